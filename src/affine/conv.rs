@@ -0,0 +1,14 @@
+/// Conversion boilerplate
+use super::Affine;
+
+impl From<kurbo::Affine> for Affine {
+    fn from(affine: kurbo::Affine) -> Self {
+        Affine::new(affine.as_coeffs())
+    }
+}
+
+impl From<Affine> for kurbo::Affine {
+    fn from(affine: Affine) -> Self {
+        kurbo::Affine::new(affine.coefficients)
+    }
+}
@@ -0,0 +1,154 @@
+mod conv;
+#[cfg(feature = "skia")]
+mod skia;
+
+use std::ops::Mul;
+
+use crate::Vector;
+
+// Determinants smaller than this make a transform impossible to undo.
+const SINGULAR_EPSILON: f64 = 1e-12;
+
+/// A 2×3 affine transformation matrix. The coefficients [a, b, c, d, e, f] are in the same order as
+/// kurbo's and SVG's, and map (x, y) to (ax + cy + e, bx + dy + f).
+///
+/// Multiplying two transforms gives the transform that applies the right hand one first, so
+/// `a * b` is `b.then(a)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine {
+    pub coefficients: [f64; 6],
+}
+
+impl Affine {
+    pub const IDENTITY: Affine = Affine {
+        coefficients: [1., 0., 0., 1., 0., 0.],
+    };
+
+    pub fn new(coefficients: [f64; 6]) -> Self {
+        Affine { coefficients }
+    }
+
+    pub fn translate(offset: Vector) -> Self {
+        Affine::new([1., 0., 0., 1., offset.x, offset.y])
+    }
+
+    pub fn scale(factors: Vector) -> Self {
+        Affine::new([factors.x, 0., 0., factors.y, 0., 0.])
+    }
+
+    /// Rotates counter clockwise around the origin, the angle is in radians.
+    pub fn rotate(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Affine::new([cos, sin, -sin, cos, 0., 0.])
+    }
+
+    pub fn rotate_about(angle: f64, pivot: Vector) -> Self {
+        Affine::translate(-pivot)
+            .then(Affine::rotate(angle))
+            .then(Affine::translate(pivot))
+    }
+
+    /// Skews by the given factors, x moves by skew.x for every unit of y and y by skew.y for every
+    /// unit of x. The factors are the tangents of the skew angles.
+    pub fn skew(skew: Vector) -> Self {
+        Affine::new([1., skew.y, skew.x, 1., 0., 0.])
+    }
+
+    /// Mirrors across the line through point with the given direction.
+    pub fn reflect(point: Vector, direction: Vector) -> Self {
+        let direction = direction.normalize();
+        let (x, y) = (direction.x, direction.y);
+
+        Affine::translate(-point)
+            .then(Affine::new([
+                x * x - y * y,
+                2. * x * y,
+                2. * x * y,
+                y * y - x * x,
+                0.,
+                0.,
+            ]))
+            .then(Affine::translate(point))
+    }
+
+    /// The transform that applies this one and then other.
+    pub fn then(self, other: Affine) -> Self {
+        let [a1, b1, c1, d1, e1, f1] = self.coefficients;
+        let [a2, b2, c2, d2, e2, f2] = other.coefficients;
+
+        Affine::new([
+            a2 * a1 + c2 * b1,
+            b2 * a1 + d2 * b1,
+            a2 * c1 + c2 * d1,
+            b2 * c1 + d2 * d1,
+            a2 * e1 + c2 * f1 + e2,
+            b2 * e1 + d2 * f1 + f2,
+        ])
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let [a, b, c, d, _, _] = self.coefficients;
+
+        a * d - b * c
+    }
+
+    /// Returns the transform that undoes this one, or None if it squashes the plane flat.
+    pub fn inverse(&self) -> Option<Self> {
+        let [a, b, c, d, e, f] = self.coefficients;
+        let determinant = self.determinant();
+        if determinant.abs() <= SINGULAR_EPSILON {
+            return None;
+        }
+
+        Some(Affine::new([
+            d / determinant,
+            -b / determinant,
+            -c / determinant,
+            a / determinant,
+            (c * f - d * e) / determinant,
+            (b * e - a * f) / determinant,
+        ]))
+    }
+
+    pub fn apply(&self, point: Vector) -> Vector {
+        let [a, b, c, d, e, f] = self.coefficients;
+
+        Vector {
+            x: a * point.x + c * point.y + e,
+            y: b * point.x + d * point.y + f,
+        }
+    }
+
+    /// Applies the transform to a direction, which the translation doesn't affect.
+    pub fn apply_to_direction(&self, direction: Vector) -> Vector {
+        let [a, b, c, d, _, _] = self.coefficients;
+
+        Vector {
+            x: a * direction.x + c * direction.y,
+            y: b * direction.x + d * direction.y,
+        }
+    }
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Affine::IDENTITY
+    }
+}
+
+impl Mul for Affine {
+    type Output = Affine;
+
+    fn mul(self, other: Affine) -> Affine {
+        other.then(self)
+    }
+}
+
+impl Mul<Vector> for Affine {
+    type Output = Vector;
+
+    fn mul(self, point: Vector) -> Vector {
+        self.apply(point)
+    }
+}
@@ -0,0 +1,26 @@
+use super::Affine;
+use skia_safe::Matrix;
+
+// skia's matrices are 3×3 and can hold a perspective, that part gets dropped when converting.
+impl From<&Matrix> for Affine {
+    fn from(matrix: &Matrix) -> Self {
+        Affine::new([
+            matrix.scale_x() as f64,
+            matrix.skew_y() as f64,
+            matrix.skew_x() as f64,
+            matrix.scale_y() as f64,
+            matrix.translate_x() as f64,
+            matrix.translate_y() as f64,
+        ])
+    }
+}
+
+impl From<Affine> for Matrix {
+    fn from(affine: Affine) -> Self {
+        let [a, b, c, d, e, f] = affine.coefficients;
+
+        Matrix::new_all(
+            a as f32, c as f32, e as f32, b as f32, d as f32, f as f32, 0., 0., 1.,
+        )
+    }
+}
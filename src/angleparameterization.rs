@@ -1,6 +1,8 @@
 // Add your relevant imports here
 use super::evaluate::Evaluate;
 use super::parameterization::Parameterization;
+use crate::arclenparameterization::search_for_index;
+use crate::error::{MathError, MathResult};
 
 #[derive(Debug, Clone)]
 pub struct AngleParameterization {
@@ -40,43 +42,18 @@ impl AngleParameterization {
     }
 
     fn search_for_index(&self, target: f64) -> usize {
-        let mut left = 0;
-        let mut right = self.total_angles.len() - 1;
-
-        while left < right {
-            let middle = (right + left) / 2;
-
-            if left == middle {
-                return middle;
-            }
-            if right == middle {
-                return left;
-            }
-            if self.total_angles[middle] == target {
-                return middle;
-            }
-
-            if self.total_angles[middle] < target {
-                left = middle;
-            } else {
-                right = middle;
-            }
-        }
-
-        panic!("Couldn't find the target angle!")
+        search_for_index(&self.total_angles, target)
     }
 
     pub fn get_angle_from_t(&self, t: f64) -> f64 {
-        let fractional_index = t * (self.total_angles.len() - 1) as f64;
-        let index = fractional_index as usize;
+        let intervals = self.total_angles.len() - 1;
+        let fractional_index = t * intervals as f64;
+        // t = 1 is the end of the last interval rather than the start of one past it
+        let index = (fractional_index as usize).min(intervals - 1);
         let fraction = fractional_index - index as f64;
 
         let angle_start = self.total_angles[index];
-        let angle_end = if index != self.total_angles.len() - 1 {
-            self.total_angles[index + 1]
-        } else {
-            1.
-        };
+        let angle_end = self.total_angles[index + 1];
         let segment_angle = angle_end - angle_start;
 
         angle_start + segment_angle * fraction
@@ -162,4 +139,29 @@ impl Parameterization for AngleParameterization {
             return (angle_index as f64 + segment_fraction) / (self.total_angles.len() - 1) as f64;
         }
     }
+
+    // The table needs at least a start and an end to look anything up in.
+    fn try_parameterize(&self, u: f64) -> MathResult<f64> {
+        if self.total_angles.len() < 2 {
+            return Err(MathError::EmptyParameterization);
+        }
+        if !u.is_finite() {
+            return Err(MathError::InvalidParameter(u));
+        }
+
+        Ok(self.parameterize(u))
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        let total = self.get_total_angle();
+        if total == 0. {
+            return t;
+        }
+
+        self.get_angle_from_t(t) / total
+    }
+
+    fn total(&self) -> f64 {
+        self.get_total_angle()
+    }
 }
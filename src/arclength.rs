@@ -0,0 +1,247 @@
+use crate::conic::Conic;
+use crate::ellipticalarc::EllipticalArc;
+use crate::line::Line;
+use crate::quadbezier::QuadBezier;
+use crate::segment::Segment;
+use crate::{Bezier, Evaluate, Piecewise};
+
+// Five point Gauss-Legendre nodes and weights moved to 0-1.
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (0.046910077030668, 0.118463442528095),
+    (0.2307653449471585, 0.2393143352496832),
+    (0.5, 0.2844444444444444),
+    (0.7692346550528415, 0.2393143352496832),
+    (0.953089922969332, 0.118463442528095),
+];
+
+// How many times an interval can be halved before we settle for the estimate we have.
+const MAX_DEPTH: usize = 24;
+
+// Newton steps before falling back on bisection alone.
+const MAX_ITERATIONS: usize = 32;
+
+/// Lengths along a curve, measured by adaptive Gauss-Legendre quadrature of its speed. Every
+/// length is accurate to within the tolerance it's asked for.
+pub trait ArcLength {
+    /// How fast the curve moves at t, the length of its derivative.
+    fn speed_at(&self, t: f64) -> f64;
+
+    /// The length of the curve between t0 and t1. Negative if t1 comes before t0.
+    fn arclen_between(&self, t0: f64, t1: f64, tolerance: f64) -> f64 {
+        integrate(&|t| self.speed_at(t), t0, t1, tolerance)
+    }
+
+    fn arclen(&self, tolerance: f64) -> f64 {
+        self.arclen_between(0., 1., tolerance)
+    }
+
+    /// The t that's length along the curve from its start, found with Newton's method. Lengths
+    /// past either end of the curve give 0 or 1.
+    fn t_at_arclen(&self, length: f64, tolerance: f64) -> f64 {
+        invert(self, 0., 1., length, tolerance)
+    }
+}
+
+// The integral of f between t0 and t1. Each interval is compared against the sum of its halves,
+// and halved until they agree to within the tolerance.
+pub(crate) fn integrate<F: Fn(f64) -> f64>(f: &F, t0: f64, t1: f64, tolerance: f64) -> f64 {
+    let whole = gauss_legendre(f, t0, t1);
+    integrate_adaptive(f, t0, t1, whole, tolerance, MAX_DEPTH)
+}
+
+fn integrate_adaptive<F: Fn(f64) -> f64>(
+    f: &F,
+    t0: f64,
+    t1: f64,
+    whole: f64,
+    tolerance: f64,
+    depth: usize,
+) -> f64 {
+    let middle = (t0 + t1) / 2.;
+    let left = gauss_legendre(f, t0, middle);
+    let right = gauss_legendre(f, middle, t1);
+
+    if depth == 0 || (left + right - whole).abs() <= tolerance {
+        return left + right;
+    }
+
+    integrate_adaptive(f, t0, middle, left, tolerance / 2., depth - 1)
+        + integrate_adaptive(f, middle, t1, right, tolerance / 2., depth - 1)
+}
+
+fn gauss_legendre<F: Fn(f64) -> f64>(f: &F, t0: f64, t1: f64) -> f64 {
+    let width = t1 - t0;
+    GAUSS_LEGENDRE
+        .iter()
+        .map(|&(t, weight)| weight * f(t0 + t * width))
+        .sum::<f64>()
+        * width
+}
+
+// Finds the t between t0 and t1 that's length along the curve from t0. Newton's method is kept
+// inside a bracket around the answer, and steps that would leave it bisect the bracket instead.
+pub(crate) fn invert<T: ArcLength + ?Sized>(
+    curve: &T,
+    t0: f64,
+    t1: f64,
+    length: f64,
+    tolerance: f64,
+) -> f64 {
+    if length <= 0. {
+        return t0;
+    }
+    let total = curve.arclen_between(t0, t1, tolerance);
+    if length >= total {
+        return t1;
+    }
+
+    let (mut low, mut high) = (t0, t1);
+    let mut t = t0 + (t1 - t0) * length / total;
+    for _ in 0..MAX_ITERATIONS {
+        let error = curve.arclen_between(t0, t, tolerance / 2.) - length;
+        if error.abs() <= tolerance {
+            return t;
+        }
+
+        if error > 0. {
+            high = t;
+        } else {
+            low = t;
+        }
+
+        let speed = curve.speed_at(t);
+        let next = t - error / speed;
+        t = if speed > 0. && next > low && next < high {
+            next
+        } else {
+            (low + high) / 2.
+        };
+    }
+
+    t
+}
+
+impl ArcLength for Bezier {
+    fn speed_at(&self, t: f64) -> f64 {
+        self.tangent_at(t).magnitude()
+    }
+}
+
+// The speed of a quadratic is the square root of a quadratic, which has an antiderivative.
+impl ArcLength for QuadBezier {
+    fn speed_at(&self, t: f64) -> f64 {
+        self.tangent_at(t).magnitude()
+    }
+
+    fn arclen_between(&self, t0: f64, t1: f64, tolerance: f64) -> f64 {
+        // B'(t) = 2(at + b), so speed² = 4(At² + Bt + C)
+        let a = self.w1 - self.w2 * 2. + self.w3;
+        let b = self.w2 - self.w1;
+        let (qa, qb, qc) = (a.dot(a), 2. * a.dot(b), b.dot(b));
+        let discriminant = 4. * qa * qc - qb * qb;
+
+        // a straight quadratic has a zero under the logarithm, so it's left to the quadrature
+        if qa <= f64::EPSILON * qc || discriminant <= 1e-9 * qa * qc {
+            return integrate(&|t| self.speed_at(t), t0, t1, tolerance);
+        }
+
+        let antiderivative = |t: f64| {
+            let q = ((qa * t + qb) * t + qc).sqrt();
+            let root_a = qa.sqrt();
+            (2. * qa * t + qb) * q / (4. * qa)
+                + discriminant / (8. * qa * root_a) * (2. * root_a * q + 2. * qa * t + qb).ln()
+        };
+
+        2. * (antiderivative(t1) - antiderivative(t0))
+    }
+}
+
+impl ArcLength for Line {
+    fn speed_at(&self, t: f64) -> f64 {
+        self.tangent_at(t).magnitude()
+    }
+
+    fn arclen_between(&self, t0: f64, t1: f64, _tolerance: f64) -> f64 {
+        self.w1.distance(self.w2) * (t1 - t0)
+    }
+}
+
+impl ArcLength for Segment {
+    fn speed_at(&self, t: f64) -> f64 {
+        self.tangent_at(t).magnitude()
+    }
+
+    fn arclen_between(&self, t0: f64, t1: f64, tolerance: f64) -> f64 {
+        match self {
+            Segment::Line(line) => line.arclen_between(t0, t1, tolerance),
+            Segment::Quad(quad) => quad.arclen_between(t0, t1, tolerance),
+            Segment::Cubic(bez) => bez.arclen_between(t0, t1, tolerance),
+        }
+    }
+}
+
+impl ArcLength for Conic {
+    fn speed_at(&self, t: f64) -> f64 {
+        self.tangent_at(t).magnitude()
+    }
+}
+
+impl ArcLength for EllipticalArc {
+    fn speed_at(&self, t: f64) -> f64 {
+        self.tangent_at(t).magnitude()
+    }
+}
+
+// Lengths are in terms of the piecewise's own t, so they follow its cuts. Each segment is
+// measured on its own, the speed can jump where they meet.
+impl<T: Evaluate + ArcLength> ArcLength for Piecewise<T> {
+    fn speed_at(&self, t: f64) -> f64 {
+        let n = self.seg_n(t);
+        let width = self.cuts[n + 1] - self.cuts[n];
+        // a segment with no t doesn't move anywhere as t changes
+        if width == 0. {
+            return 0.;
+        }
+
+        self.segs[n].speed_at(self.seg_t(t)) / width
+    }
+
+    fn arclen_between(&self, t0: f64, t1: f64, tolerance: f64) -> f64 {
+        if t1 < t0 {
+            return -self.arclen_between(t1, t0, tolerance);
+        }
+        if self.segs.is_empty() {
+            return 0.;
+        }
+
+        let (first, last) = (self.seg_n(t0), self.seg_n(t1));
+        let tolerance = tolerance / (last - first + 1) as f64;
+        (first..=last)
+            .map(|n| {
+                let start = if n == first { self.seg_t(t0) } else { 0. };
+                let end = if n == last { self.seg_t(t1) } else { 1. };
+                self.segs[n].arclen_between(start, end, tolerance)
+            })
+            .sum()
+    }
+
+    fn arclen(&self, tolerance: f64) -> f64 {
+        match (self.cuts.first(), self.cuts.last()) {
+            (Some(&first), Some(&last)) => self.arclen_between(first, last, tolerance),
+            _ => 0.,
+        }
+    }
+
+    fn t_at_arclen(&self, length: f64, tolerance: f64) -> f64 {
+        let mut remaining = length;
+        for (n, seg) in self.segs.iter().enumerate() {
+            let seg_length = seg.arclen(tolerance);
+            if remaining < seg_length || n == self.segs.len() - 1 {
+                return self.global_t(n, seg.t_at_arclen(remaining, tolerance));
+            }
+            remaining -= seg_length;
+        }
+
+        self.cuts.first().copied().unwrap_or(0.)
+    }
+}
@@ -1,5 +1,15 @@
 use super::evaluate::Evaluate;
 use super::parameterization::Parameterization;
+use crate::arclength::ArcLength;
+use crate::error::{MathError, MathResult};
+
+// The number of intervals a table measured with from_arclength starts with, and how many times
+// one can be halved to meet its tolerance.
+const MIN_INTERVALS: usize = 8;
+const MAX_DEPTH: usize = 30;
+
+// Newton steps when looking up a length within one interval of the table.
+const MAX_ITERATIONS: usize = 32;
 
 // We build a table of total arc length along the line and use it to map 0-1
 // to the arclength of the curve such that 0.5 is halfway along the curve by arc-length
@@ -36,52 +46,183 @@ impl ArcLengthParameterization {
         return *self.arclens.last().unwrap();
     }
 
-    // Have to implement a custom binary search here because we're looking
-    // not for an exact index but the index of the highest value that's less than
-    // the target
     fn search_for_index(&self, target: f64) -> usize {
-        let mut left = 0;
-        let mut right = self.arclens.len() - 1;
+        search_for_index(&self.arclens, target)
+    }
+
+    pub fn get_arclen_from_t(&self, t: f64) -> f64 {
+        // A table with fewer than two entries has nothing to interpolate between
+        if self.arclens.len() < 2 {
+            return 0.;
+        }
+
+        let intervals = self.arclens.len() - 1;
+        let fractional_index = t * intervals as f64;
+        let index = (fractional_index.max(0.) as usize).min(intervals - 1);
+        let fraction = fractional_index - index as f64;
+
+        let len_start = self.arclens[index];
+        let segment_len = self.arclens[index + 1] - len_start;
+
+        len_start + segment_len * fraction
+    }
+}
+
+/// An arc length table measured with [`ArcLength`] rather than with chords. Its entries are placed
+/// wherever they're needed to keep lookups within a tolerance of the curve's actual length, and
+/// between them the length follows a cubic through the speeds at their ends.
+#[derive(Debug, Clone)]
+pub struct AdaptiveArcLengthParameterization {
+    arclens: Vec<f64>,
+    // How fast the curve moves at the start and end of each interval of the table.
+    speeds: Vec<(f64, f64)>,
+    // The t of each entry of arclens.
+    ts: Vec<f64>,
+}
+
+impl AdaptiveArcLengthParameterization {
+    /// Measures the curve with ArcLength, splitting it into smaller intervals wherever that's
+    /// needed for looking up any length in the table to be within tolerance of the curve's actual
+    /// length there. Where the speed jumps, like at the joins of a piecewise, the intervals get
+    /// tiny, everywhere else they stay wide.
+    pub fn from_arclength<T: ArcLength>(curve: &T, tolerance: f64) -> Self {
+        let mut table = Self {
+            arclens: vec![0.],
+            speeds: Vec::new(),
+            ts: vec![0.],
+        };
+
+        let step = 1. / MIN_INTERVALS as f64;
+        for i in 0..MIN_INTERVALS {
+            let (t0, t1) = (i as f64 * step, (i + 1) as f64 * step);
+            table.measure(curve, t0, t1, tolerance, MAX_DEPTH);
+        }
+
+        table
+    }
 
-        while left < right {
-            let middle = (right + left) / 2;
+    // Adds the interval from t0 to t1 to the table, or halves it if the cubic across it is more
+    // than half the tolerance out. The lengths at the ends of the intervals are measured to within
+    // a share of the other half, so their errors can't add up to more than it.
+    fn measure<T: ArcLength>(&mut self, curve: &T, t0: f64, t1: f64, tolerance: f64, depth: usize) {
+        let width = t1 - t0;
+        let length_tolerance = tolerance * width / 4.;
+        let length = curve.arclen_between(t0, t1, length_tolerance);
+        // the speeds are taken just inside the interval, so a jump in speed right at one of its
+        // ends doesn't spoil the cubic
+        let nudge = width * 1e-6;
+        let speed = (curve.speed_at(t0 + nudge), curve.speed_at(t1 - nudge));
 
-            if left == middle {
-                return middle;
+        let middle = (t0 + t1) / 2.;
+        if depth > 0 {
+            let half = curve.arclen_between(t0, middle, length_tolerance);
+            if (hermite(length, speed, width, 0.5) - half).abs() > tolerance / 2. {
+                self.measure(curve, t0, middle, tolerance, depth - 1);
+                self.measure(curve, middle, t1, tolerance, depth - 1);
+                return;
             }
-            if right == middle {
-                return left;
+        }
+
+        let start = self.arclens[self.arclens.len() - 1];
+        self.arclens.push(start + length);
+        self.speeds.push(speed);
+        self.ts.push(t1);
+    }
+
+    pub fn get_total_arclen(&self) -> f64 {
+        self.arclens[self.arclens.len() - 1]
+    }
+
+    pub fn get_arclen_from_t(&self, t: f64) -> f64 {
+        let index = search_for_index(&self.ts, t).min(self.ts.len() - 2);
+        let (t0, t1) = (self.ts[index], self.ts[index + 1]);
+        let fraction = (t - t0) / (t1 - t0);
+
+        let len_start = self.arclens[index];
+        let segment_len = self.arclens[index + 1] - len_start;
+
+        len_start + hermite(segment_len, self.speeds[index], t1 - t0, fraction)
+    }
+
+    // The fraction of the way across an interval of the table that's length along it. Newton's
+    // method on the interval's cubic, kept inside a bracket around the answer.
+    fn invert_interval(&self, index: usize, length: f64) -> f64 {
+        let segment_len = self.arclens[index + 1] - self.arclens[index];
+        let speed = self.speeds[index];
+        let step = self.ts[index + 1] - self.ts[index];
+
+        let (mut low, mut high) = (0., 1.);
+        let mut fraction = length / segment_len;
+        for _ in 0..MAX_ITERATIONS {
+            let error = hermite(segment_len, speed, step, fraction) - length;
+            if error.abs() <= segment_len * 1e-12 {
+                break;
             }
-            if self.arclens[middle] == target {
-                return middle;
-            };
 
-            if self.arclens[middle] < target {
-                left = middle
+            if error > 0. {
+                high = fraction;
             } else {
-                right = middle;
+                low = fraction;
             }
+
+            let next = fraction - error / hermite_derivative(segment_len, speed, step, fraction);
+            fraction = if next > low && next < high {
+                next
+            } else {
+                (low + high) / 2.
+            };
         }
 
-        // This needs to be replaced with success/failure.
-        panic!("Couldn't find the target arc length!")
+        fraction
     }
+}
 
-    pub fn get_arclen_from_t(&self, t: f64) -> f64 {
-        let fractional_index = t * (self.arclens.len() - 1) as f64;
-        let index = fractional_index as usize;
-        let fraction = fractional_index - index as f64;
+// Have to implement a custom binary search here because we're looking
+// not for an exact index but the index of the highest value that's less than
+// the target. The angle and curvature tables are searched the same way.
+pub(crate) fn search_for_index(values: &[f64], target: f64) -> usize {
+    let mut left = 0;
+    let mut right = values.len() - 1;
 
-        let len_start = self.arclens[index];
-        let len_end = if index != self.arclens.len() - 1 {
-            self.arclens[index + 1]
-        } else {
-            1.
+    while left < right {
+        let middle = (right + left) / 2;
+
+        if left == middle {
+            return middle;
+        }
+        if right == middle {
+            return left;
+        }
+        if values[middle] == target {
+            return middle;
         };
-        let segment_len = len_start - len_end;
 
-        return len_start + segment_len * fraction;
+        if values[middle] < target {
+            left = middle
+        } else {
+            right = middle;
+        }
     }
+
+    // This needs to be replaced with success/failure.
+    panic!("Couldn't find the target value!")
+}
+
+// The length a fraction of the way across an interval of a table that's step wide in t, from the
+// cubic with its length and the speeds at its ends.
+fn hermite(length: f64, speed: (f64, f64), step: f64, fraction: f64) -> f64 {
+    let f = fraction;
+    (f * f * f - 2. * f * f + f) * speed.0 * step
+        + (3. * f * f - 2. * f * f * f) * length
+        + (f * f * f - f * f) * speed.1 * step
+}
+
+// The derivative of hermite by fraction.
+fn hermite_derivative(length: f64, speed: (f64, f64), step: f64, fraction: f64) -> f64 {
+    let f = fraction;
+    (3. * f * f - 4. * f + 1.) * speed.0 * step
+        + (6. * f - 6. * f * f) * length
+        + (3. * f * f - 2. * f) * speed.1 * step
 }
 
 impl Parameterization for ArcLengthParameterization {
@@ -101,4 +242,66 @@ impl Parameterization for ArcLengthParameterization {
             return (arclen_index as f64 + segment_fraction) / (self.arclens.len() - 1) as f64;
         }
     }
+
+    // The table needs at least a start and an end to look anything up in.
+    fn try_parameterize(&self, u: f64) -> MathResult<f64> {
+        if self.arclens.len() < 2 {
+            return Err(MathError::EmptyParameterization);
+        }
+        if !u.is_finite() {
+            return Err(MathError::InvalidParameter(u));
+        }
+
+        Ok(self.parameterize(u))
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        let total = self.get_total_arclen();
+        if total == 0. {
+            return t;
+        }
+
+        self.get_arclen_from_t(t) / total
+    }
+
+    fn total(&self) -> f64 {
+        self.get_total_arclen()
+    }
+}
+
+impl Parameterization for AdaptiveArcLengthParameterization {
+    fn parameterize(&self, u: f64) -> f64 {
+        let target_arclen = u * self.get_total_arclen();
+
+        let index = search_for_index(&self.arclens, target_arclen);
+        if target_arclen == self.arclens[index] {
+            return self.ts[index];
+        }
+
+        let fraction = self.invert_interval(index, target_arclen - self.arclens[index]);
+        let (t0, t1) = (self.ts[index], self.ts[index + 1]);
+        t0 + fraction * (t1 - t0)
+    }
+
+    // The table always has a start and an end, only u can be wrong.
+    fn try_parameterize(&self, u: f64) -> MathResult<f64> {
+        if !u.is_finite() {
+            return Err(MathError::InvalidParameter(u));
+        }
+
+        Ok(self.parameterize(u))
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        let total = self.get_total_arclen();
+        if total == 0. {
+            return t;
+        }
+
+        self.get_arclen_from_t(t) / total
+    }
+
+    fn total(&self) -> f64 {
+        self.get_total_arclen()
+    }
 }
@@ -0,0 +1,181 @@
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+use crate::quadbezier::QuadBezier;
+use crate::segment::Segment;
+use crate::{Bezier, Evaluate, Piecewise, Vector};
+
+// Gauss-Legendre nodes and weights moved to 0-1. Six points integrate polynomials up to degree 11
+// exactly, and x³y' of a cubic is the highest degree integrand we need.
+const GAUSS_LEGENDRE: [(f64, f64); 6] = [
+    (0.033765242898423975, 0.0856622461895852),
+    (0.16939530676686776, 0.1803807865240693),
+    (0.3806904069584015, 0.2339569672863455),
+    (0.6193095930415985, 0.2339569672863455),
+    (0.8306046932331322, 0.1803807865240693),
+    (0.966234757101576, 0.0856622461895852),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// The area of a shape and its first and second moments of area, all signed the way the area is.
+/// x and y are the integrals of x and y over the shape, xx, xy and yy those of x², xy and y².
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AreaMoments {
+    pub area: f64,
+    pub x: f64,
+    pub y: f64,
+    pub xx: f64,
+    pub xy: f64,
+    pub yy: f64,
+}
+
+impl AreaMoments {
+    /// The center of mass of the shape, None if it has no area.
+    pub fn centroid(&self) -> Option<Vector> {
+        if self.area == 0. {
+            return None;
+        }
+
+        Some(Vector::from_components(
+            self.x / self.area,
+            self.y / self.area,
+        ))
+    }
+
+    /// The second moments about the centroid rather than the origin, in the order xx, xy, yy.
+    pub fn central_second_moments(&self) -> Option<(f64, f64, f64)> {
+        let c = self.centroid()?;
+
+        Some((
+            self.xx - self.area * c.x * c.x,
+            self.xy - self.area * c.x * c.y,
+            self.yy - self.area * c.y * c.y,
+        ))
+    }
+
+    // Green's theorem turns each integral over the shape into one along its outline, which for a
+    // polynomial curve is the integral of a polynomial in t.
+    fn of_cubic(bez: &Bezier) -> Self {
+        let [a, b, c, d] = bez.power_coefficients();
+        let mut moments = AreaMoments::default();
+
+        for &(t, weight) in &GAUSS_LEGENDRE {
+            let p = ((a * t + b) * t + c) * t + d;
+            let dp = (a * 3. * t + b * 2.) * t + c;
+
+            moments.area += weight * (p.x * dp.y - p.y * dp.x) / 2.;
+            moments.x += weight * p.x * p.x * dp.y / 2.;
+            moments.y -= weight * p.y * p.y * dp.x / 2.;
+            moments.xx += weight * p.x * p.x * p.x * dp.y / 3.;
+            moments.xy += weight * p.x * p.x * p.y * dp.y / 2.;
+            moments.yy -= weight * p.y * p.y * p.y * dp.x / 3.;
+        }
+
+        moments
+    }
+
+    // Sums the moments along a contour, closing it with a line if it doesn't end where it starts.
+    fn of_contour<I: IntoIterator<Item = Bezier>>(cubics: I) -> Self {
+        let mut moments = AreaMoments::default();
+        let mut ends: Option<(Vector, Vector)> = None;
+
+        for bez in cubics {
+            moments += AreaMoments::of_cubic(&bez);
+            ends = Some((ends.map_or(bez.w1, |(start, _)| start), bez.w4));
+        }
+
+        if let Some((start, end)) = ends {
+            if start != end {
+                moments += AreaMoments::of_cubic(&Bezier::from_points(end, end, start, start));
+            }
+        }
+
+        moments
+    }
+}
+
+impl Add for AreaMoments {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        AreaMoments {
+            area: self.area + other.area,
+            x: self.x + other.x,
+            y: self.y + other.y,
+            xx: self.xx + other.xx,
+            xy: self.xy + other.xy,
+            yy: self.yy + other.yy,
+        }
+    }
+}
+
+impl AddAssign for AreaMoments {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sum for AreaMoments {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(AreaMoments::default(), Add::add)
+    }
+}
+
+/// Exact areas and moments of closed paths. Contours that don't end where they start are closed
+/// with a straight line. Counter clockwise contours have a positive area, and the contours of an
+/// outline are summed, so holes running against their outer contour get taken out of it.
+pub trait Area {
+    fn moments(&self) -> AreaMoments;
+
+    fn signed_area(&self) -> f64 {
+        self.moments().area
+    }
+
+    /// None if the path encloses no area, or as much clockwise as counter clockwise area.
+    fn orientation(&self) -> Option<Orientation> {
+        let area = self.signed_area();
+        if area > 0. {
+            Some(Orientation::CounterClockwise)
+        } else if area < 0. {
+            Some(Orientation::Clockwise)
+        } else {
+            None
+        }
+    }
+
+    fn centroid(&self) -> Option<Vector> {
+        self.moments().centroid()
+    }
+}
+
+impl Area for Piecewise<Bezier> {
+    fn moments(&self) -> AreaMoments {
+        AreaMoments::of_contour(self.segs.iter().cloned())
+    }
+}
+
+impl Area for Piecewise<QuadBezier> {
+    fn moments(&self) -> AreaMoments {
+        AreaMoments::of_contour(self.segs.iter().map(QuadBezier::to_cubic))
+    }
+}
+
+impl Area for Piecewise<Segment> {
+    fn moments(&self) -> AreaMoments {
+        AreaMoments::of_contour(self.segs.iter().map(Segment::to_cubic))
+    }
+}
+
+impl<T: Evaluate + Send + Sync> Area for Piecewise<Piecewise<T>>
+where
+    Piecewise<T>: Area,
+{
+    fn moments(&self) -> AreaMoments {
+        self.segs.iter().map(Area::moments).sum()
+    }
+}
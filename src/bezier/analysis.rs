@@ -0,0 +1,165 @@
+use super::Bezier;
+use crate::consts::SMALL_T_DISTANCE;
+use crate::roots::{interior_roots, solve_quadratic};
+use crate::subdivide::Subdivide;
+use crate::Piecewise;
+
+// Cross products smaller than this (relative to the squared size of the coefficients) are treated
+// as zero.
+const DEGENERATE_EPSILON: f64 = 1e-12;
+
+/// The shape of the part of a cubic between t = 0 and t = 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CubicType {
+    /// Has one or two inflections.
+    Serpentine,
+    /// Crosses itself.
+    Loop,
+    /// Comes to a point where the derivative vanishes.
+    Cusp,
+    /// Bends one way only.
+    Arch,
+    /// All of the control points are collinear.
+    Line,
+}
+
+impl Bezier {
+    // The cross products of the power basis coefficients, (a x b, c x a, c x b). Everything about
+    // the shape of the curve can be read off of these.
+    fn shape_coefficients(&self) -> (f64, f64, f64) {
+        let [a, b, c, _] = self.power_coefficients();
+
+        (a.cross(b), c.cross(a), c.cross(b))
+    }
+
+    fn is_degenerate(&self, coefficient: f64) -> bool {
+        let [a, b, c, _] = self.power_coefficients();
+        let scale = f64::max(a.dot(a), f64::max(b.dot(b), c.dot(c)));
+
+        coefficient.abs() <= scale * DEGENERATE_EPSILON
+    }
+
+    /// Returns the t values strictly inside the curve where its curvature changes sign.
+    pub fn inflections(&self) -> Vec<f64> {
+        let (ab, ca, cb) = self.shape_coefficients();
+
+        // B'(t) x B''(t) = 0 works out to -3(a x b)t^2 + 3(c x a)t + (c x b) = 0
+        interior_roots(solve_quadratic(-3. * ab, 3. * ca, cb))
+    }
+
+    /// Returns the pair of t values at which the curve crosses itself, if it does so between
+    /// t = 0 and t = 1.
+    pub fn self_intersection(&self) -> Option<(f64, f64)> {
+        let (s, t) = self.double_point()?;
+
+        // when the two parameters meet it's a cusp instead
+        if s - t <= SMALL_T_DISTANCE || t < 0. || s > 1. {
+            return None;
+        }
+
+        Some((t, s))
+    }
+
+    /// Returns the t value of the cusp, if the curve has one between t = 0 and t = 1.
+    pub fn cusp(&self) -> Option<f64> {
+        let (s, t) = self.double_point()?;
+        let cusp = (s + t) / 2.;
+
+        if s - t > SMALL_T_DISTANCE || cusp <= 0. || cusp >= 1. {
+            return None;
+        }
+
+        Some(cusp)
+    }
+
+    // Finds s > t with B(s) = B(t) on the infinite curve. Dividing B(s) - B(t) = 0 through by s - t
+    // gives a(s^2 + st + t^2) + b(s + t) + c = 0, crossing that with a and b gives us the sum and
+    // the product of s and t. A cusp is the limit where s and t meet.
+    fn double_point(&self) -> Option<(f64, f64)> {
+        let (ab, ca, cb) = self.shape_coefficients();
+        if self.is_degenerate(ab) {
+            return None;
+        }
+
+        let sum = ca / ab;
+        let product = sum * sum + cb / ab;
+
+        let discriminant = sum * sum - 4. * product;
+        if discriminant < -SMALL_T_DISTANCE * SMALL_T_DISTANCE {
+            return None;
+        }
+
+        let spread = discriminant.max(0.).sqrt();
+        Some(((sum + spread) / 2., (sum - spread) / 2.))
+    }
+
+    pub fn classify(&self) -> CubicType {
+        let (ab, ca, cb) = self.shape_coefficients();
+
+        if self.is_degenerate(ab) && self.is_degenerate(ca) && self.is_degenerate(cb) {
+            CubicType::Line
+        } else if self.cusp().is_some() {
+            CubicType::Cusp
+        } else if self.self_intersection().is_some() {
+            CubicType::Loop
+        } else if !self.inflections().is_empty() {
+            CubicType::Serpentine
+        } else {
+            CubicType::Arch
+        }
+    }
+
+    /// The t values at which the curve needs to be split so that every piece is an arch or a line.
+    /// Loops are split at both ends and in the middle so that neither half of the loop turns
+    /// through more than half a circle.
+    pub fn critical_t_values(&self) -> Vec<f64> {
+        let mut t_values = self.inflections();
+
+        if let Some(t) = self.cusp() {
+            t_values.push(t);
+        }
+
+        if let Some((t1, t2)) = self.self_intersection() {
+            t_values.extend(interior_roots(vec![t1, (t1 + t2) / 2., t2]));
+        }
+
+        t_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        t_values.dedup_by(|a, b| (*a - *b).abs() <= SMALL_T_DISTANCE);
+        t_values
+    }
+}
+
+impl Piecewise<Bezier> {
+    /// Splits every segment at its inflections, cusps and loops, see [`Bezier::critical_t_values`].
+    /// The new segments divide the cuts of the segment they came from in proportion to their t
+    /// ranges, so the piecewise still evaluates the same.
+    pub fn split_at_critical_points(&self) -> Self {
+        let mut new_segs = Vec::new();
+        let mut new_cuts = vec![self.cuts[0]];
+
+        for (i, bez) in self.segs.iter().enumerate() {
+            let t_values = bez.critical_t_values();
+
+            for t in &t_values {
+                new_cuts.push(self.global_t(i, *t));
+            }
+            new_cuts.push(self.cuts[i + 1]);
+
+            new_segs.extend(bez.split_at_multiple_t(t_values));
+        }
+
+        Piecewise::new(new_segs, Some(new_cuts))
+    }
+}
+
+impl Piecewise<Piecewise<Bezier>> {
+    pub fn split_at_critical_points(&self) -> Self {
+        let new_segments = self
+            .segs
+            .iter()
+            .map(|piecewise| piecewise.split_at_critical_points())
+            .collect();
+
+        Piecewise::new(new_segments, Some(self.cuts.clone()))
+    }
+}
@@ -33,6 +33,12 @@ impl Evaluate for Bezier {
         tangent
     }
 
+    fn second_derivative_at(&self, t: f64) -> Vector {
+        let [a, b, _, _] = self.power_coefficients();
+
+        a * (6. * t) + b * 2.
+    }
+
     fn apply_transform<F>(&self, transform: F) -> Self
     where
         F: Fn(&Vector) -> Vector,
@@ -48,8 +54,17 @@ impl Evaluate for Bezier {
         return Bezier::from_points(tp[0], tp[1], tp[2], tp[3]);
     }
 
+    // The control points only give us the hull, so we evaluate the curve at its extrema to get the
+    // tightest box that still contains every point on the curve.
     fn bounds(&self) -> Rect {
-        return Rect::AABB_from_points(self.to_control_points_vec());
+        let (x_extrema, y_extrema) = self.extrema();
+
+        let mut points = vec![self.w1, self.w4];
+        for t in x_extrema.into_iter().chain(y_extrema) {
+            points.push(self.at(t));
+        }
+
+        return Rect::AABB_from_points(points);
     }
 
     fn start_point(&self) -> Vector {
@@ -0,0 +1,31 @@
+use super::Bezier;
+use crate::roots::{interior_roots, solve_quadratic};
+use crate::Vector;
+
+impl Bezier {
+    // Returns the coefficients [a, b, c, d] of the power basis form of the curve,
+    // B(t) = a*t^3 + b*t^2 + c*t + d
+    pub fn power_coefficients(&self) -> [Vector; 4] {
+        let [p0, p1, p2, p3] = self.to_control_points();
+
+        [
+            -p0 + p1 * 3. - p2 * 3. + p3,
+            p0 * 3. - p1 * 6. + p2 * 3.,
+            (p1 - p0) * 3.,
+            p0,
+        ]
+    }
+
+    /// Returns the t values where the derivative of x and the derivative of y vanish, in that
+    /// order. Only extrema strictly inside the curve are reported as the end points are always
+    /// extreme with respect to the curve's bounds anyway.
+    pub fn extrema(&self) -> (Vec<f64>, Vec<f64>) {
+        let [a, b, c, _] = self.power_coefficients();
+
+        // B'(t) = 3a*t^2 + 2b*t + c
+        let x_extrema = interior_roots(solve_quadratic(3. * a.x, 2. * b.x, c.x));
+        let y_extrema = interior_roots(solve_quadratic(3. * a.y, 2. * b.y, c.y));
+
+        (x_extrema, y_extrema)
+    }
+}
@@ -3,8 +3,13 @@ use crate::{subdivide::Subdivide, Evaluate};
 use super::vector::Vector;
 use glifparser::{Point as GPPoint, PointData as GPPointData};
 
+mod analysis;
 mod evaluate;
+mod extrema;
 mod flo;
+mod offset;
+
+pub use self::analysis::CubicType;
 
 #[derive(Clone, Debug)]
 #[allow(non_snake_case)]
@@ -78,7 +83,28 @@ impl Subdivide for Bezier {
             return None;
         }
 
-        // Perform De Casteljau's algorithm to split the curve at t
+        Some(self.de_casteljau(t))
+    }
+
+    // Two splits at most, and none for an end that's already at 0 or 1. The second split is at t0
+    // of what's left, which has t1 of the original as its 1.
+    fn subsegment(&self, t0: f64, t1: f64) -> Bezier {
+        let head = if t1 < 1. {
+            self.de_casteljau(t1).0
+        } else {
+            self.clone()
+        };
+        if t0 <= 0. || t1 <= 0. {
+            return head;
+        }
+
+        head.de_casteljau(t0 / t1.min(1.)).1
+    }
+}
+
+impl Bezier {
+    // Perform De Casteljau's algorithm to split the curve at t
+    fn de_casteljau(&self, t: f64) -> (Bezier, Bezier) {
         let w12 = self.w1.lerp(self.w2, t);
         let w23 = self.w2.lerp(self.w3, t);
         let w34 = self.w3.lerp(self.w4, t);
@@ -91,6 +117,6 @@ impl Subdivide for Bezier {
         let first_half = Bezier::from_points(self.w1, w12, w123, w1234);
         let second_half = Bezier::from_points(w1234, w234, w34, self.w4);
 
-        Some((first_half, second_half))
+        (first_half, second_half)
     }
 }
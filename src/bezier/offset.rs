@@ -0,0 +1,65 @@
+use super::Bezier;
+use crate::fit_to_points::fit_cubic_with_tangents;
+use crate::nearest::NearestPoint;
+use crate::subdivide::Subdivide;
+use crate::{Evaluate, Vector};
+
+// How many points along the true offset each fitted cubic is fitted to and checked against.
+const OFFSET_SAMPLES: usize = 8;
+
+// Pieces this many halvings deep are kept even if they're not within the tolerance. That only
+// happens where the offset has a cusp, which no cubic can follow.
+const MAX_OFFSET_DEPTH: usize = 8;
+
+impl Bezier {
+    /// Approximates the curve that runs at a constant distance from this one, positive distances
+    /// being on the side of normal_at, that's to the right. The curve is split at its inflections,
+    /// cusps and loops first, and then halved until a cubic fits the true offset of each piece
+    /// within the tolerance.
+    pub fn offset(&self, distance: f64, tolerance: f64) -> Vec<Bezier> {
+        if distance == 0. {
+            return vec![self.clone()];
+        }
+
+        self.split_at_multiple_t(self.critical_t_values())
+            .iter()
+            .flat_map(|piece| offset_piece(piece, distance, tolerance, 0))
+            .collect()
+    }
+}
+
+fn offset_piece(bez: &Bezier, distance: f64, tolerance: f64, depth: usize) -> Vec<Bezier> {
+    let ts: Vec<f64> = (0..=OFFSET_SAMPLES)
+        .map(|i| i as f64 / OFFSET_SAMPLES as f64)
+        .collect();
+    let points: Vec<Vector> = ts
+        .iter()
+        .map(|&t| bez.at(t) + bez.normal_at(t) * distance)
+        .collect();
+
+    let fitted = fit_cubic_with_tangents(&points, &ts, bez.tangent_at(0.), bez.tangent_at(1.));
+    if depth >= MAX_OFFSET_DEPTH || offset_error(bez, &fitted, distance) <= tolerance {
+        return vec![fitted];
+    }
+
+    match bez.split(0.5) {
+        Some((left, right)) => {
+            let mut output = offset_piece(&left, distance, tolerance, depth + 1);
+            output.extend(offset_piece(&right, distance, tolerance, depth + 1));
+            output
+        }
+        None => vec![fitted],
+    }
+}
+
+// How far off the fitted curve strays from being at the distance from the original, checked in
+// between the points it was fitted to.
+fn offset_error(bez: &Bezier, fitted: &Bezier, distance: f64) -> f64 {
+    (0..OFFSET_SAMPLES)
+        .map(|i| {
+            let t = (i as f64 + 0.5) / OFFSET_SAMPLES as f64;
+            let (_, _, actual) = bez.nearest_point(fitted.at(t));
+            (actual - distance.abs()).abs()
+        })
+        .fold(0., f64::max)
+}
@@ -0,0 +1,24 @@
+use glifparser::{Glif, PointData};
+
+use crate::error::{MathError, MathResult};
+use crate::segment::Segment;
+use crate::Piecewise;
+
+/// Runs [`Piecewise::remove_overlaps`] on the glyph's outline, everything else about the glyph is
+/// kept as it is. Lines stay lines and curves stay curves, though a curve whose handles lie on the
+/// straight line between its ends comes back as a line.
+pub fn remove_overlaps_glif<PD: PointData>(glif: &Glif<PD>) -> Glif<PD> {
+    try_remove_overlaps_glif(glif)
+        .unwrap_or_else(|err| panic!("Couldn't remove the glyph's overlaps: {}", err))
+}
+
+pub fn try_remove_overlaps_glif<PD: PointData>(glif: &Glif<PD>) -> MathResult<Glif<PD>> {
+    let outline: Piecewise<Piecewise<Segment>> =
+        Piecewise::from(glif.outline.as_ref().ok_or(MathError::MissingOutline)?);
+    let merged = outline.to_cubic().remove_overlaps();
+
+    Ok(Glif {
+        outline: Some(Piecewise::<Piecewise<Segment>>::from(&merged).to_outline()),
+        ..glif.clone()
+    })
+}
@@ -0,0 +1,284 @@
+mod glif;
+
+use crate::area::Area;
+use crate::consts::{SMALL_DISTANCE, SMALL_T_DISTANCE};
+use crate::intersection::Intersect;
+use crate::reverse::Reverse;
+use crate::subdivide::Subdivide;
+use crate::winding::{MonotonicOutline, Winding};
+use crate::{Bezier, Evaluate, Piecewise, Rect, Vector};
+
+pub use self::glif::{remove_overlaps_glif, try_remove_overlaps_glif};
+
+// Points this far to either side of an edge tell us what's on each side of it.
+const SIDE_DISTANCE: f64 = SMALL_DISTANCE;
+
+// Edge ends closer than this are the same vertex, and edges that don't get further from their
+// start than this are dropped.
+const JOIN_DISTANCE: f64 = SMALL_DISTANCE;
+
+/// The boolean operations on filled outlines. Both operands are filled with the nonzero rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    /// What's in the first operand but not the second.
+    Difference,
+    /// What's in exactly one of the operands.
+    Xor,
+}
+
+impl BooleanOp {
+    fn keeps(self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Intersection => in_a && in_b,
+            BooleanOp::Difference => in_a && !in_b,
+            BooleanOp::Xor => in_a != in_b,
+        }
+    }
+}
+
+impl Piecewise<Piecewise<Bezier>> {
+    /// Combines two outlines. Contours that aren't closed are closed with a line first. The
+    /// result's contours run counter clockwise around filled areas and clockwise around holes, and
+    /// stay curves: every edge of it is a piece of an edge of one of the operands.
+    pub fn boolean(&self, other: &Self, op: BooleanOp) -> Self {
+        let outlines = [MonotonicOutline::from(self), MonotonicOutline::from(other)];
+        let segments = closed_segments(self)
+            .chain(closed_segments(other))
+            .collect();
+
+        resolve(segments, |point| {
+            op.keeps(
+                outlines[0].winding_number(point) != 0,
+                outlines[1].winding_number(point) != 0,
+            )
+        })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Union)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Intersection)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Difference)
+    }
+
+    pub fn xor(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Xor)
+    }
+
+    /// Merges contours that overlap and untangles contours that cross themselves, filling with the
+    /// nonzero rule. Segments that don't cross anything come through as they are, so the on-curve
+    /// points survive wherever the outline doesn't change. The contours run the same way the
+    /// input's outer contours do.
+    pub fn remove_overlaps(&self) -> Self {
+        let outline = MonotonicOutline::from(self);
+        let result = resolve(closed_segments(self).collect(), |point| {
+            outline.winding_number(point) != 0
+        });
+
+        if self.signed_area() < 0. {
+            Piecewise::new(result.segs.iter().map(Reverse::reverse).collect(), None)
+        } else {
+            result
+        }
+    }
+}
+
+// The segments of all of the contours, with a closing line added to the ones that need it.
+pub(crate) fn closed_segments(
+    outline: &Piecewise<Piecewise<Bezier>>,
+) -> impl Iterator<Item = Bezier> + '_ {
+    outline.segs.iter().flat_map(|contour| {
+        let closing = match (contour.segs.first(), contour.segs.last()) {
+            (Some(first), Some(last)) if first.w1 != last.w4 => {
+                Some(Bezier::from_points(last.w4, last.w4, first.w1, first.w1))
+            }
+            _ => None,
+        };
+
+        contour.segs.iter().cloned().chain(closing)
+    })
+}
+
+// Cuts the segments up where they cross, keeps the pieces that have the inside of the result on
+// exactly one side of them and joins those into contours. The pieces are turned around as needed
+// so the inside is always on their left.
+pub(crate) fn resolve<F: Fn(Vector) -> bool>(
+    segments: Vec<Bezier>,
+    inside: F,
+) -> Piecewise<Piecewise<Bezier>> {
+    let mut edges: Vec<Bezier> = Vec::new();
+
+    for edge in split_at_intersections(&segments) {
+        if is_tiny(&edge) {
+            continue;
+        }
+
+        let middle = edge.at(0.5);
+        let right = edge.tangent_at(0.5).right_normal() * SIDE_DISTANCE;
+        let edge = match (inside(middle - right), inside(middle + right)) {
+            (true, false) => edge,
+            (false, true) => edge.reverse(),
+            _ => continue,
+        };
+
+        // where the operands have an edge in common we only want it once
+        if !edges.iter().any(|kept| is_same_edge(kept, &edge)) {
+            edges.push(edge);
+        }
+    }
+
+    Piecewise::new(join_edges(edges), None)
+}
+
+fn split_at_intersections(segments: &[Bezier]) -> Vec<Bezier> {
+    let bounds: Vec<Rect> = segments.iter().map(Evaluate::bounds).collect();
+    let mut splits: Vec<Vec<(f64, Vector)>> = vec![Vec::new(); segments.len()];
+
+    for (i, seg) in segments.iter().enumerate() {
+        if let Some((s, t)) = seg.self_intersection() {
+            let point = seg.at(s);
+            splits[i].push((s, point));
+            splits[i].push((t, point));
+        }
+
+        for j in i + 1..segments.len() {
+            if !bounds_touch(&bounds[i], &bounds[j]) {
+                continue;
+            }
+
+            for (t_i, t_j, point) in seg.intersections(&segments[j]) {
+                splits[i].push((t_i, point));
+                splits[j].push((t_j, point));
+            }
+        }
+    }
+
+    segments
+        .iter()
+        .zip(splits)
+        .flat_map(|(seg, splits)| split_and_snap(seg, splits))
+        .collect()
+}
+
+// Splits the segment at the given t values and moves the ends of the pieces onto the points the
+// intersections were found at, so that pieces of different segments meet exactly. Splits are only
+// merged when their t values are close, a cubic that crosses itself has two far apart t values at
+// the same point and needs cutting at both.
+fn split_and_snap(seg: &Bezier, mut splits: Vec<(f64, Vector)>) -> Vec<Bezier> {
+    splits.retain(|(t, _)| *t > SMALL_T_DISTANCE && *t < 1. - SMALL_T_DISTANCE);
+    splits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    splits.dedup_by(|b, a| b.0 - a.0 <= SMALL_T_DISTANCE);
+
+    let mut pieces = seg.split_at_multiple_t(splits.iter().map(|(t, _)| *t).collect());
+    if pieces.len() == splits.len() + 1 {
+        for (i, (_, point)) in splits.iter().enumerate() {
+            move_end(&mut pieces[i], *point);
+            move_start(&mut pieces[i + 1], *point);
+        }
+    }
+
+    pieces
+}
+
+// The handles move along with the points so the tangents stay the same.
+fn move_start(bez: &mut Bezier, point: Vector) {
+    bez.w2 += point - bez.w1;
+    bez.w1 = point;
+}
+
+fn move_end(bez: &mut Bezier, point: Vector) {
+    bez.w3 += point - bez.w4;
+    bez.w4 = point;
+}
+
+fn bounds_touch(a: &Rect, b: &Rect) -> bool {
+    a.left - JOIN_DISTANCE <= b.right
+        && b.left - JOIN_DISTANCE <= a.right
+        && a.bottom - JOIN_DISTANCE <= b.top
+        && b.bottom - JOIN_DISTANCE <= a.top
+}
+
+fn is_tiny(bez: &Bezier) -> bool {
+    [bez.w2, bez.w3, bez.w4]
+        .iter()
+        .all(|point| point.is_near(bez.w1, JOIN_DISTANCE))
+}
+
+fn is_same_edge(a: &Bezier, b: &Bezier) -> bool {
+    a.w1.is_near(b.w1, JOIN_DISTANCE)
+        && a.w4.is_near(b.w4, JOIN_DISTANCE)
+        && a.at(0.5).is_near(b.at(0.5), JOIN_DISTANCE)
+}
+
+// The direction the curve leaves its start in, ignoring handles that sit on the point.
+fn start_direction(bez: &Bezier) -> Vector {
+    [bez.w2, bez.w3, bez.w4]
+        .iter()
+        .find(|point| !point.is_near(bez.w1, JOIN_DISTANCE))
+        .map_or(bez.w4 - bez.w1, |point| *point - bez.w1)
+}
+
+fn end_direction(bez: &Bezier) -> Vector {
+    -start_direction(&bez.reverse())
+}
+
+// Follows the edges from end to start until they come back around. Where more than one edge
+// leaves a vertex we take the one turning furthest left, which keeps contours that only touch at
+// a point apart.
+fn join_edges(edges: Vec<Bezier>) -> Vec<Piecewise<Bezier>> {
+    let mut used = vec![false; edges.len()];
+    let mut contours = Vec::new();
+
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+
+        let start = edges[first].w1;
+        let mut contour = vec![edges[first].clone()];
+
+        loop {
+            let last = contour.last_mut().unwrap();
+            if last.w4.is_near(start, JOIN_DISTANCE) {
+                move_end(last, start);
+                break;
+            }
+
+            let (end, incoming) = (last.w4, end_direction(last));
+            let next = (0..edges.len())
+                .filter(|&i| !used[i] && edges[i].w1.is_near(end, JOIN_DISTANCE))
+                .map(|i| {
+                    let outgoing = start_direction(&edges[i]);
+                    (
+                        i,
+                        f64::atan2(incoming.cross(outgoing), incoming.dot(outgoing)),
+                    )
+                })
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+            match next {
+                Some((i, _)) => {
+                    used[i] = true;
+                    let mut edge = edges[i].clone();
+                    move_start(&mut edge, end);
+                    contour.push(edge);
+                }
+                // only happens when the edges didn't quite meet up, we leave the contour open
+                None => break,
+            }
+        }
+
+        contours.push(Piecewise::new(contour, None));
+    }
+
+    contours
+}
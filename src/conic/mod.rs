@@ -0,0 +1,253 @@
+use crate::error::{MathError, MathResult};
+use crate::roots::solve_polynomial;
+use crate::{subdivide::Subdivide, Bezier, Evaluate, Rect, Vector};
+
+// Hard limit on how often to_cubics halves a conic, each halving cuts the error by a factor of
+// about 64 so this is only ever reached with an unreasonable tolerance.
+const MAX_DEPTH: usize = 16;
+
+// Rational quadratic bezier in standard form, the end points have weight 1 and the control point
+// has the given weight. A weight below 1 gives an ellipse, 1 a parabola (an ordinary quadratic) and
+// above 1 a hyperbola. This is what skia uses for circles and round joins.
+#[derive(Clone, Debug)]
+pub struct Conic {
+    pub w1: Vector,
+    pub w2: Vector,
+    pub w3: Vector,
+    pub weight: f64,
+}
+
+impl Conic {
+    pub fn from_points(p0: Vector, p1: Vector, p2: Vector, weight: f64) -> Self {
+        Conic {
+            w1: p0,
+            w2: p1,
+            w3: p2,
+            weight,
+        }
+    }
+
+    pub fn to_control_points(&self) -> [Vector; 3] {
+        [self.w1, self.w2, self.w3]
+    }
+
+    pub fn reverse(&self) -> Self {
+        Conic::from_points(self.w3, self.w2, self.w1, self.weight)
+    }
+
+    // Numerator and denominator of the rational curve in power basis form, highest power first.
+    fn numerator(&self) -> [Vector; 3] {
+        let w2 = self.w2 * self.weight;
+
+        [self.w1 - w2 * 2. + self.w3, (w2 - self.w1) * 2., self.w1]
+    }
+
+    fn denominator(&self) -> [f64; 3] {
+        [2. - 2. * self.weight, 2. * self.weight - 2., 1.]
+    }
+
+    /// Returns the part of the conic between t0 and t1, reparameterized to 0-1. Blossoming the
+    /// homogeneous control points gives us the piece exactly, we just have to bring the weights
+    /// back into standard form afterwards.
+    pub fn subsegment(&self, t0: f64, t1: f64) -> Self {
+        // homogeneous control points as (x * w, y * w, w)
+        let points = [
+            (self.w1, 1.),
+            (self.w2 * self.weight, self.weight),
+            (self.w3, 1.),
+        ];
+        let blossom = |u: f64, v: f64| {
+            let basis = [(1. - u) * (1. - v), (1. - u) * v + u * (1. - v), u * v];
+            points.iter().zip(basis.iter()).fold(
+                (Vector { x: 0., y: 0. }, 0.),
+                |(p, w), ((point, weight), b)| (p + *point * *b, w + weight * b),
+            )
+        };
+
+        let (p0, w0) = blossom(t0, t0);
+        let (p1, w1) = blossom(t0, t1);
+        let (p2, w2) = blossom(t1, t1);
+
+        Conic::from_points(p0 / w0, p1 / w1, p2 / w2, w1 / (w0 * w2).sqrt())
+    }
+
+    /// Approximates the conic with cubics that stay within tolerance of it. A single cubic gets
+    /// its handles at k = 4w / (3(1 + w)) along the control polygon, which matches the conic's end
+    /// tangents and its midpoint. If that isn't close enough we halve the conic and try again.
+    pub fn to_cubics(&self, tolerance: f64) -> Vec<Bezier> {
+        self.try_to_cubics(tolerance)
+            .unwrap_or_else(|err| panic!("Couldn't convert the conic to cubics: {}", err))
+    }
+
+    /// Fallible version of to_cubics. The tolerance has to be finite and above zero, and the
+    /// control points and weight finite.
+    pub fn try_to_cubics(&self, tolerance: f64) -> MathResult<Vec<Bezier>> {
+        if !tolerance.is_finite() || tolerance <= 0. {
+            return Err(MathError::InvalidTolerance(tolerance));
+        }
+        let finite = |point: &Vector| point.x.is_finite() && point.y.is_finite();
+        if !self.to_control_points().iter().all(finite) || !self.weight.is_finite() {
+            return Err(MathError::NonFinite);
+        }
+
+        let mut output = Vec::new();
+        self.to_cubics_recursive(tolerance, 0, &mut output);
+        Ok(output)
+    }
+
+    fn to_cubics_recursive(&self, tolerance: f64, depth: usize, output: &mut Vec<Bezier>) {
+        let k = 4. * self.weight / (3. * (1. + self.weight));
+        let cubic = Bezier::from_points(
+            self.w1,
+            self.w1.lerp(self.w2, k),
+            self.w3.lerp(self.w2, k),
+            self.w3,
+        );
+
+        // the curves don't share a parameterization, but comparing them at the same t can only
+        // overestimate the distance between them
+        let error = (1..16)
+            .map(|i| {
+                let t = i as f64 / 16.;
+                cubic.at(t).distance(self.at(t))
+            })
+            .fold(0., f64::max);
+
+        if error <= tolerance || depth >= MAX_DEPTH {
+            output.push(cubic);
+            return;
+        }
+
+        let (first, second) = self.split(0.5).unwrap();
+        first.to_cubics_recursive(tolerance, depth + 1, output);
+        second.to_cubics_recursive(tolerance, depth + 1, output);
+    }
+
+    /// Returns the t values where the derivative of x and the derivative of y vanish, in that
+    /// order. Like the other primitives only extrema strictly inside the curve are reported.
+    pub fn extrema(&self) -> (Vec<f64>, Vec<f64>) {
+        let [a, b, c] = self.numerator();
+        let denominator = self.denominator();
+
+        // (N/D)' = 0 where N'D - ND' = 0, the cubic terms cancel so this is at most a quadratic
+        let extrema = |n: [f64; 3]| {
+            let n_prime = [2. * n[0], n[1]];
+            let d_prime = [2. * denominator[0], denominator[1]];
+
+            let mut coefficients = [0.; 4];
+            for (i, np) in n_prime.iter().enumerate() {
+                for (j, d) in denominator.iter().enumerate() {
+                    coefficients[i + j] += np * d;
+                }
+            }
+            for (i, nc) in n.iter().enumerate() {
+                for (j, dp) in d_prime.iter().enumerate() {
+                    coefficients[i + j] -= nc * dp;
+                }
+            }
+
+            solve_polynomial(&coefficients, 0., 1.)
+                .into_iter()
+                .filter(|t| *t > 0. && *t < 1.)
+                .collect()
+        };
+
+        (extrema([a.x, b.x, c.x]), extrema([a.y, b.y, c.y]))
+    }
+}
+
+impl Evaluate for Conic {
+    fn at(&self, t: f64) -> Vector {
+        let [a, b, c] = self.numerator();
+        let [d2, d1, d0] = self.denominator();
+
+        ((a * t + b) * t + c) / ((d2 * t + d1) * t + d0)
+    }
+
+    fn tangent_at(&self, t: f64) -> Vector {
+        let [a, b, _] = self.numerator();
+        let [d2, d1, d0] = self.denominator();
+
+        let d = (d2 * t + d1) * t + d0;
+        let d_prime = 2. * d2 * t + d1;
+
+        // (N/D)' = (N' - P D') / D
+        (a * (2. * t) + b - self.at(t) * d_prime) / d
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector {
+        let [a, _, _] = self.numerator();
+        let [d2, d1, d0] = self.denominator();
+
+        let d = (d2 * t + d1) * t + d0;
+        let d_prime = 2. * d2 * t + d1;
+        let d_prime2 = 2. * d2;
+
+        // (N/D)'' = (N'' - 2P'D' - P D'') / D
+        (a * 2. - self.tangent_at(t) * (2. * d_prime) - self.at(t) * d_prime2) / d
+    }
+
+    fn bounds(&self) -> Rect {
+        let (x_extrema, y_extrema) = self.extrema();
+
+        let mut points = vec![self.w1, self.w3];
+        for t in x_extrema.into_iter().chain(y_extrema) {
+            points.push(self.at(t));
+        }
+
+        Rect::AABB_from_points(points)
+    }
+
+    // Conics are invariant under affine transforms so we can just transform the control points,
+    // anything more exotic than that will only be approximated.
+    fn apply_transform<F>(&self, transform: F) -> Self
+    where
+        F: Fn(&Vector) -> Vector,
+    {
+        Conic::from_points(
+            transform(&self.w1),
+            transform(&self.w2),
+            transform(&self.w3),
+            self.weight,
+        )
+    }
+
+    fn start_point(&self) -> Vector {
+        self.w1
+    }
+
+    fn end_point(&self) -> Vector {
+        self.w3
+    }
+}
+
+impl Subdivide for Conic {
+    fn split(&self, t: f64) -> Option<(Conic, Conic)> {
+        if t == 1. || t == 0. {
+            return None;
+        }
+
+        Some((self.subsegment(0., t), self.subsegment(t, 1.)))
+    }
+
+    fn subsegment(&self, t0: f64, t1: f64) -> Conic {
+        Conic::subsegment(self, t0, t1)
+    }
+
+    // Splitting a conic changes the parameterization of the pieces in a non-linear way, so rather
+    // than splitting the pieces again we cut each one straight out of the original.
+    fn split_at_multiple_t(&self, mut t_values: Vec<f64>) -> Vec<Self> {
+        t_values.retain(|t| *t > 0. && *t < 1.);
+        t_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        t_values.dedup();
+
+        let mut bounds = vec![0.];
+        bounds.extend(t_values);
+        bounds.push(1.);
+
+        bounds
+            .windows(2)
+            .map(|pair| self.subsegment(pair[0], pair[1]))
+            .collect()
+    }
+}
@@ -0,0 +1,144 @@
+use super::evaluate::Evaluate;
+use super::parameterization::Parameterization;
+use crate::arclenparameterization::search_for_index;
+use crate::error::{MathError, MathResult};
+
+// We build a table of how far along the curve we are, where far is a blend of arc length and how
+// much the curve has turned. Both are measured as a fraction of their total over the curve, so a
+// weight of 0 gives an arc length parameterization and a weight of 1 one by angle. Anything in
+// between puts more of 0-1 where the curve bends without leaving the straight parts empty.
+#[derive(Debug, Clone)]
+pub struct CurvatureParameterization {
+    pub totals: Vec<f64>,
+    pub weight: f64,
+}
+
+impl CurvatureParameterization {
+    /// An evaluable, an accuracy value which defines how many lines we use to measure it, and the
+    /// weight of curvature against arc length between 0 and 1.
+    pub fn from<T: Evaluate>(evaluable: &T, iterations: i32, weight: f64) -> Self {
+        let mut lengths = Vec::new();
+        let mut angles = Vec::new();
+
+        let mut prev_point = evaluable.at(0.0);
+        let mut prev_tangent = evaluable.tangent_at(0.0);
+        for i in 1..=iterations {
+            let t = i as f64 / iterations as f64;
+            let point = evaluable.at(t);
+            let tangent = evaluable.tangent_at(t);
+
+            lengths.push(point.distance(prev_point));
+            // the integral of |curvature| along an interval is how far the tangent turns over it
+            angles.push(f64::atan2(prev_tangent.cross(tangent), prev_tangent.dot(tangent)).abs());
+
+            prev_point = point;
+            prev_tangent = tangent;
+        }
+
+        let total_length: f64 = lengths.iter().sum();
+        let total_angle: f64 = angles.iter().sum();
+        let weight = weight.clamp(0., 1.);
+        // a straight curve has nothing to weigh against its length
+        let blend = if total_angle > 0. { weight } else { 0. };
+
+        let mut output = vec![0.0];
+        let mut sum = 0.0;
+        for (length, angle) in lengths.iter().zip(&angles) {
+            let mut step = 0.;
+            if total_length > 0. {
+                step += (1. - blend) * length / total_length;
+            }
+            if blend > 0. {
+                step += blend * angle / total_angle;
+            }
+
+            sum += step;
+            output.push(sum);
+        }
+
+        Self {
+            totals: output,
+            weight,
+        }
+    }
+
+    pub fn get_total(&self) -> f64 {
+        *self.totals.last().unwrap()
+    }
+
+    fn search_for_index(&self, target: f64) -> usize {
+        search_for_index(&self.totals, target)
+    }
+
+    /// The t values that split the curve into pieces interval long by this measure, where the
+    /// whole curve is 1 long.
+    pub fn find_parameters_for_intervals(&self, interval: f64) -> Vec<f64> {
+        let mut curve_parameters = Vec::new();
+        if interval <= 0. {
+            return curve_parameters;
+        }
+
+        let mut u = interval;
+        while u < 1. {
+            curve_parameters.push(self.parameterize(u));
+            u += interval;
+        }
+        curve_parameters
+    }
+}
+
+impl Parameterization for CurvatureParameterization {
+    fn parameterize(&self, u: f64) -> f64 {
+        let total = self.get_total();
+        if total == 0. {
+            return u;
+        }
+
+        let target = u * total;
+        let index = self.search_for_index(target);
+        let intervals = (self.totals.len() - 1) as f64;
+
+        if target == self.totals[index] {
+            index as f64 / intervals
+        } else {
+            let start = self.totals[index];
+            let end = self.totals[index + 1];
+            let fraction = (target - start) / (end - start);
+
+            (index as f64 + fraction) / intervals
+        }
+    }
+
+    // The table needs at least a start and an end to look anything up in.
+    fn try_parameterize(&self, u: f64) -> MathResult<f64> {
+        if self.totals.len() < 2 {
+            return Err(MathError::EmptyParameterization);
+        }
+        if !u.is_finite() {
+            return Err(MathError::InvalidParameter(u));
+        }
+
+        Ok(self.parameterize(u))
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        let total = self.get_total();
+        if total == 0. {
+            return t;
+        }
+
+        let intervals = self.totals.len() - 1;
+        let fractional_index = t * intervals as f64;
+        let index = (fractional_index as usize).min(intervals - 1);
+        let fraction = fractional_index - index as f64;
+
+        let start = self.totals[index];
+        let end = self.totals[index + 1];
+
+        (start + (end - start) * fraction) / total
+    }
+
+    fn total(&self) -> f64 {
+        self.get_total()
+    }
+}
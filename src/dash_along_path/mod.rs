@@ -14,6 +14,8 @@ use skia_safe as skia;
 
 use log;
 
+use crate::{Bezier, Piecewise};
+
 fn add_flutter_to_dash_description(desc: &mut Vec<f32>, slen: f32) -> f32 {
     let dash_len = desc.iter().sum::<f32>();
     let gap_len = desc.iter().skip(1).step_by(2).sum::<f32>();
@@ -140,8 +142,10 @@ pub fn dash_along_path<PD: PointData>(
                     final_skpath = fsk;
                 }
                 None => {
-                    log::error!("Ran into a skia::PathOp::Union that refused to resolve. This is likely a Skia bug; downgrading to overlapping splines. (Consider testing w/MFEKpathops BOOLEAN)");
-                    final_skpath.add_path(&skp_o_s, (0., 0.), None);
+                    log::warn!("Ran into a skia::PathOp::Union that refused to resolve. This is likely a Skia bug; falling back to our own union.");
+                    let union = Piecewise::<Piecewise<Bezier>>::from(&final_skpath)
+                        .union(&Piecewise::from(&skp_o_s));
+                    final_skpath = union.to_skpath();
                 }
             }
         } else {
@@ -0,0 +1,340 @@
+use std::f64::consts;
+
+use crate::error::{MathError, MathResult};
+use crate::{subdivide::Subdivide, Bezier, Evaluate, Rect, Vector};
+
+// Part of an ellipse, parameterized by angle. The ellipse is centered on center with radii.x along
+// its own x axis and radii.y along its own y axis, and it's turned by rotation. The arc starts at
+// start_angle and covers sweep_angle, counter clockwise if that's positive. All angles are in
+// radians and t runs linearly from the start angle to the end angle.
+#[derive(Clone, Debug)]
+pub struct EllipticalArc {
+    pub center: Vector,
+    pub radii: Vector,
+    pub rotation: f64,
+    pub start_angle: f64,
+    pub sweep_angle: f64,
+}
+
+impl EllipticalArc {
+    pub fn new(
+        center: Vector,
+        radii: Vector,
+        rotation: f64,
+        start_angle: f64,
+        sweep_angle: f64,
+    ) -> Self {
+        EllipticalArc {
+            center,
+            radii,
+            rotation,
+            start_angle,
+            sweep_angle,
+        }
+    }
+
+    /// Builds the arc an SVG path's arc command draws from from to to, using the conversion from
+    /// the SVG implementation notes. x_rotation is in degrees like it is in SVG, and radii that are
+    /// too small to reach to are scaled up. Returns None where SVG wouldn't draw an arc: when the
+    /// end points are the same or a radius is zero, in which case it draws a straight line.
+    pub fn from_svg(
+        from: Vector,
+        rx: f64,
+        ry: f64,
+        x_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        to: Vector,
+    ) -> Option<Self> {
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if from == to || rx == 0. || ry == 0. {
+            return None;
+        }
+
+        let rotation = x_rotation.to_radians();
+        let origin = Vector { x: 0., y: 0. };
+
+        // move into the ellipse's frame, with the origin halfway between the end points
+        let p = ((from - to) / 2.).rotate(origin, -rotation);
+
+        let lambda = (p.x * p.x) / (rx * rx) + (p.y * p.y) / (ry * ry);
+        if lambda > 1. {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let numerator = rx * rx * ry * ry - rx * rx * p.y * p.y - ry * ry * p.x * p.x;
+        let denominator = rx * rx * p.y * p.y + ry * ry * p.x * p.x;
+        let mut coefficient = (numerator / denominator).max(0.).sqrt();
+        if large_arc == sweep {
+            coefficient = -coefficient;
+        }
+
+        let center = Vector {
+            x: coefficient * rx * p.y / ry,
+            y: -coefficient * ry * p.x / rx,
+        };
+
+        let start = Vector {
+            x: (p.x - center.x) / rx,
+            y: (p.y - center.y) / ry,
+        };
+        let end = Vector {
+            x: (-p.x - center.x) / rx,
+            y: (-p.y - center.y) / ry,
+        };
+
+        let start_angle = f64::atan2(start.y, start.x);
+        let mut sweep_angle = start.angle(end) % consts::TAU;
+        if !sweep && sweep_angle > 0. {
+            sweep_angle -= consts::TAU;
+        } else if sweep && sweep_angle < 0. {
+            sweep_angle += consts::TAU;
+        }
+
+        Some(EllipticalArc::new(
+            center.rotate(origin, rotation) + from.lerp(to, 0.5),
+            Vector { x: rx, y: ry },
+            rotation,
+            start_angle,
+            sweep_angle,
+        ))
+    }
+
+    pub fn end_angle(&self) -> f64 {
+        self.start_angle + self.sweep_angle
+    }
+
+    pub fn reverse(&self) -> Self {
+        EllipticalArc::new(
+            self.center,
+            self.radii,
+            self.rotation,
+            self.end_angle(),
+            -self.sweep_angle,
+        )
+    }
+
+    // The ellipse's semi axes, the points on it are center + u cos(angle) + v sin(angle).
+    fn axes(&self) -> (Vector, Vector) {
+        let origin = Vector { x: 0., y: 0. };
+        let u = Vector {
+            x: self.radii.x,
+            y: 0.,
+        };
+        let v = Vector {
+            x: 0.,
+            y: self.radii.y,
+        };
+
+        (
+            u.rotate(origin, self.rotation),
+            v.rotate(origin, self.rotation),
+        )
+    }
+
+    pub fn point_at_angle(&self, angle: f64) -> Vector {
+        let (u, v) = self.axes();
+
+        self.center + u * angle.cos() + v * angle.sin()
+    }
+
+    // derivative of point_at_angle with respect to the angle
+    fn derivative_at_angle(&self, angle: f64) -> Vector {
+        let (u, v) = self.axes();
+
+        v * angle.cos() - u * angle.sin()
+    }
+
+    fn angle_at(&self, t: f64) -> f64 {
+        self.start_angle + self.sweep_angle * t
+    }
+
+    /// Approximates the arc with as few cubics as will stay within tolerance of it, though never
+    /// with fewer than one per quarter turn. Every cubic gets the usual 4/3 tan(angle / 4) handles
+    /// along the ellipse's tangents, which is the image of the standard circle approximation, so
+    /// its error is at most the larger radius times that of the circle's.
+    pub fn to_cubics(&self, tolerance: f64) -> Vec<Bezier> {
+        self.try_to_cubics(tolerance)
+            .unwrap_or_else(|err| panic!("Couldn't convert the arc to cubics: {}", err))
+    }
+
+    /// Fallible version of to_cubics. The tolerance has to be finite and above zero, and the arc's
+    /// center, radii and angles finite.
+    pub fn try_to_cubics(&self, tolerance: f64) -> MathResult<Vec<Bezier>> {
+        if !tolerance.is_finite() || tolerance <= 0. {
+            return Err(MathError::InvalidTolerance(tolerance));
+        }
+        if ![
+            self.center.x,
+            self.center.y,
+            self.radii.x,
+            self.radii.y,
+            self.rotation,
+            self.start_angle,
+            self.sweep_angle,
+        ]
+        .iter()
+        .all(|value| value.is_finite())
+        {
+            return Err(MathError::NonFinite);
+        }
+
+        let radius = f64::max(self.radii.x.abs(), self.radii.y.abs());
+        let error = |angle: f64| {
+            let quarter = angle.abs() / 4.;
+            radius * 2. * quarter.sin().powi(6) / (27. * quarter.cos().powi(2))
+        };
+
+        let mut n = usize::max(
+            (self.sweep_angle.abs() / consts::FRAC_PI_2).ceil() as usize,
+            1,
+        );
+        while error(self.sweep_angle / n as f64) > tolerance {
+            n += 1;
+        }
+
+        let step = self.sweep_angle / n as f64;
+        let handle = 4. / 3. * (step / 4.).tan();
+
+        Ok((0..n)
+            .map(|i| {
+                let from = self.angle_at(i as f64 / n as f64);
+                let to = self.angle_at((i + 1) as f64 / n as f64);
+                let (p0, p3) = (self.point_at_angle(from), self.point_at_angle(to));
+
+                Bezier::from_points(
+                    p0,
+                    p0 + self.derivative_at_angle(from) * handle,
+                    p3 - self.derivative_at_angle(to) * handle,
+                    p3,
+                )
+            })
+            .collect())
+    }
+}
+
+impl Evaluate for EllipticalArc {
+    fn at(&self, t: f64) -> Vector {
+        self.point_at_angle(self.angle_at(t))
+    }
+
+    fn tangent_at(&self, t: f64) -> Vector {
+        self.derivative_at_angle(self.angle_at(t)) * self.sweep_angle
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector {
+        (self.center - self.at(t)) * (self.sweep_angle * self.sweep_angle)
+    }
+
+    fn bounds(&self) -> Rect {
+        let (u, v) = self.axes();
+        let (low, high) = if self.sweep_angle < 0. {
+            (self.end_angle(), self.start_angle)
+        } else {
+            (self.start_angle, self.end_angle())
+        };
+
+        let mut points = vec![self.start_point(), self.end_point()];
+
+        // x and y are extreme every half turn from these angles on
+        for base in [f64::atan2(v.x, u.x), f64::atan2(v.y, u.y)] {
+            let first = ((low - base) / consts::PI).ceil() as i64;
+            let last = ((high - base) / consts::PI).floor() as i64;
+
+            for k in first..=last {
+                points.push(self.point_at_angle(base + k as f64 * consts::PI));
+            }
+        }
+
+        Rect::AABB_from_points(points)
+    }
+
+    // An affine transform takes the ellipse to another ellipse, we follow what it does to the center
+    // and the two semi axes and then find the new ellipse's own axes. Transforms that aren't affine
+    // can't be represented and only get approximated this way.
+    fn apply_transform<F>(&self, transform: F) -> Self
+    where
+        F: Fn(&Vector) -> Vector,
+    {
+        let (u, v) = self.axes();
+        let center = transform(&self.center);
+        let u = transform(&(self.center + u)) - center;
+        let v = transform(&(self.center + v)) - center;
+
+        // the transformed axes are conjugate diameters, turning them by this angle makes them
+        // perpendicular again
+        let offset = 0.5 * f64::atan2(2. * u.dot(v), u.dot(u) - v.dot(v));
+        let new_u = u * offset.cos() + v * offset.sin();
+        let new_v = v * offset.cos() - u * offset.sin();
+
+        let radii = Vector {
+            x: new_u.magnitude(),
+            y: new_v.magnitude(),
+        };
+        let rotation = f64::atan2(new_u.y, new_u.x);
+
+        // a mirroring transform flips the direction the angles run in
+        if new_u.cross(new_v) < 0. {
+            EllipticalArc::new(
+                center,
+                radii,
+                rotation,
+                offset - self.start_angle,
+                -self.sweep_angle,
+            )
+        } else {
+            EllipticalArc::new(
+                center,
+                radii,
+                rotation,
+                self.start_angle - offset,
+                self.sweep_angle,
+            )
+        }
+    }
+
+    fn start_point(&self) -> Vector {
+        self.point_at_angle(self.start_angle)
+    }
+
+    fn end_point(&self) -> Vector {
+        self.point_at_angle(self.end_angle())
+    }
+}
+
+impl Subdivide for EllipticalArc {
+    fn split(&self, t: f64) -> Option<(EllipticalArc, EllipticalArc)> {
+        if t == 1. || t == 0. {
+            return None;
+        }
+
+        let split_angle = self.angle_at(t);
+        Some((
+            EllipticalArc::new(
+                self.center,
+                self.radii,
+                self.rotation,
+                self.start_angle,
+                split_angle - self.start_angle,
+            ),
+            EllipticalArc::new(
+                self.center,
+                self.radii,
+                self.rotation,
+                split_angle,
+                self.end_angle() - split_angle,
+            ),
+        ))
+    }
+
+    fn subsegment(&self, t0: f64, t1: f64) -> EllipticalArc {
+        EllipticalArc::new(
+            self.center,
+            self.radii,
+            self.rotation,
+            self.angle_at(t0),
+            self.sweep_angle * (t1 - t0),
+        )
+    }
+}
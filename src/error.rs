@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::fmt;
+
+/// Everything that can go wrong in the fallible (try_) functions of this crate. Errors that come
+/// from a part of a piecewise are wrapped in [`MathError::InSegment`], so an error from an outline
+/// tells you the contour and the segment in it that failed, see [`MathError::indices`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum MathError {
+    /// The piecewise has no segments, so it has no points, bounds or ends.
+    EmptyPiecewise,
+    /// The parameterization has no samples to look anything up in.
+    EmptyParameterization,
+    /// A t or u value that's NaN, infinite, or outside of the range it has to be in.
+    InvalidParameter(f64),
+    /// A piecewise whose cuts don't have one on either side of every segment.
+    MismatchedCuts { cuts: usize, segs: usize },
+    /// A piecewise whose cuts go backwards somewhere.
+    UnorderedCuts,
+    /// The segment with this index wouldn't start where the one before it ends.
+    Discontinuous(usize),
+    /// A tolerance that's NaN, infinite, or not above zero.
+    InvalidTolerance(f64),
+    /// A curve with a control point that's NaN or infinite.
+    NonFinite,
+    /// No approximation within tolerance could be found with as many pieces as we're willing to use.
+    NoFit,
+    /// Masters of a design that don't have the same number of contours or segments.
+    IncompatibleMasters,
+    /// A glyph that doesn't have an outline.
+    MissingOutline,
+    /// A glyph whose lib has no entry under the given key.
+    MissingLib(&'static str),
+    /// A glyph lib entry that's there but that we can't make sense of.
+    MalformedLib(String),
+    /// A variable width stroke without a handle at either end of every segment of its contour.
+    MismatchedHandles { handles: usize, segs: usize },
+    /// A custom cap was asked for but there's no cap glyph in the settings.
+    MissingCap,
+    /// Offsetting a curve didn't give us a result.
+    OffsetFailed,
+    /// The error came from the segment with this index, or the contour if this is an outline.
+    InSegment { index: usize, error: Box<MathError> },
+}
+
+pub type MathResult<T> = Result<T, MathError>;
+
+impl MathError {
+    pub(crate) fn in_segment(index: usize) -> impl FnOnce(MathError) -> MathError {
+        move |error| MathError::InSegment {
+            index,
+            error: Box::new(error),
+        }
+    }
+
+    /// The indices of the pieces the error came from, outermost first. For an outline that's the
+    /// contour followed by the segment.
+    pub fn indices(&self) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut error = self;
+        while let MathError::InSegment {
+            index,
+            error: inner,
+        } = error
+        {
+            indices.push(*index);
+            error = inner;
+        }
+
+        indices
+    }
+
+    /// The error without the segments it was wrapped in.
+    pub fn root_cause(&self) -> &MathError {
+        match self {
+            MathError::InSegment { error, .. } => error.root_cause(),
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MathError::EmptyPiecewise => write!(f, "the piecewise has no segments"),
+            MathError::EmptyParameterization => write!(f, "the parameterization has no samples"),
+            MathError::InvalidParameter(t) => write!(f, "{} isn't a valid parameter", t),
+            MathError::MismatchedCuts { cuts, segs } => {
+                write!(f, "{} cuts can't bound {} segments", cuts, segs)
+            }
+            MathError::UnorderedCuts => write!(f, "the cuts aren't in order"),
+            MathError::Discontinuous(index) => {
+                write!(f, "segment {} doesn't meet the one before it", index)
+            }
+            MathError::InvalidTolerance(tolerance) => {
+                write!(f, "{} isn't a valid tolerance", tolerance)
+            }
+            MathError::NonFinite => write!(f, "the curve has a point that isn't finite"),
+            MathError::NoFit => write!(f, "couldn't approximate the curve within tolerance"),
+            MathError::IncompatibleMasters => write!(f, "the masters aren't compatible"),
+            MathError::MissingOutline => write!(f, "the glyph has no outline"),
+            MathError::MissingLib(key) => write!(f, "the glyph's lib has no {}", key),
+            MathError::MalformedLib(reason) => write!(f, "malformed glyph lib: {}", reason),
+            MathError::MismatchedHandles { handles, segs } => {
+                write!(f, "{} handles can't stroke {} segments", handles, segs)
+            }
+            MathError::MissingCap => write!(f, "a custom cap was requested but none was given"),
+            MathError::OffsetFailed => write!(f, "couldn't offset the curve"),
+            MathError::InSegment { index, error } => write!(f, "segment {}: {}", index, error),
+        }
+    }
+}
+
+impl Error for MathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MathError::InSegment { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -1,8 +1,14 @@
+use super::affine::Affine;
 use super::rect::Rect;
 use super::vector::Vector;
 
+use crate::consts::SMALL_T_DISTANCE;
+use crate::error::MathResult;
 use crate::vec2;
 
+// Tangents shorter than this are treated as zero length.
+const DEGENERATE_TANGENT: f64 = 1e-9;
+
 // Any object in a piecewise MUST implement this trait! This trait essentially says that our struct
 // can be evaluated with respect to time t and return an x, y pair. It also needs to be able to give us
 // a derivative and a bounding box.
@@ -10,12 +16,82 @@ use crate::vec2;
 pub trait Evaluate {
     fn at(&self, t: f64) -> Vector;
     fn tangent_at(&self, u: f64) -> Vector;
+    fn second_derivative_at(&self, t: f64) -> Vector;
     fn bounds(&self) -> Rect; // returns an AABB that contains all points
     fn apply_transform<F: Send + Sync>(&self, transform: F) -> Self
     where
         F: Fn(&Vector) -> Vector;
     fn start_point(&self) -> Vector;
     fn end_point(&self) -> Vector;
+
+    // Unit normal pointing to the right of the direction of travel.
+    fn normal_at(&self, t: f64) -> Vector {
+        self.tangent_at(nondegenerate_t(self, t)).right_normal()
+    }
+
+    // Signed curvature, positive where the curve turns left (counter-clockwise). At an end whose
+    // handle sits on its point the curvature really grows without bound, so what you get there is
+    // the curvature SMALL_T_DISTANCE inside the end.
+    fn curvature_at(&self, t: f64) -> f64 {
+        let t = nondegenerate_t(self, t);
+        let d1 = self.tangent_at(t);
+        let d2 = self.second_derivative_at(t);
+
+        let speed = d1.magnitude();
+        if speed <= DEGENERATE_TANGENT {
+            return 0.;
+        }
+
+        d1.cross(d2) / (speed * speed * speed)
+    }
+
+    // Signed like the curvature. Straight parts of a curve have an infinite radius.
+    fn radius_of_curvature_at(&self, t: f64) -> f64 {
+        let curvature = self.curvature_at(t);
+        if curvature == 0. {
+            return f64::INFINITY;
+        }
+
+        1. / curvature
+    }
+
+    // Fallible versions of the above. A primitive always has an answer so by default these just
+    // wrap the infallible ones, Piecewise overrides them to report empty contours and bad
+    // parameters along with the index of the segment they came from.
+    fn try_at(&self, t: f64) -> MathResult<Vector> {
+        Ok(self.at(t))
+    }
+
+    fn try_tangent_at(&self, t: f64) -> MathResult<Vector> {
+        Ok(self.tangent_at(t))
+    }
+
+    fn try_bounds(&self) -> MathResult<Rect> {
+        Ok(self.bounds())
+    }
+
+    fn try_start_point(&self) -> MathResult<Vector> {
+        Ok(self.start_point())
+    }
+
+    fn try_end_point(&self) -> MathResult<Vector> {
+        Ok(self.end_point())
+    }
+}
+
+// When a handle sits on top of its point the derivative vanishes at that end and there's no
+// direction to take a normal or curvature from. In that case we use the values from just inside
+// the curve instead, which is what the curve visibly does there.
+pub(crate) fn nondegenerate_t<T: Evaluate + ?Sized>(curve: &T, t: f64) -> f64 {
+    if curve.tangent_at(t).magnitude() > DEGENERATE_TANGENT {
+        return t;
+    }
+
+    if t < 0.5 {
+        t + SMALL_T_DISTANCE
+    } else {
+        t - SMALL_T_DISTANCE
+    }
 }
 
 pub trait EvalTranslate: Evaluate {
@@ -30,6 +106,11 @@ pub trait EvalRotate: Evaluate {
     fn rotate(&self, angle: f64) -> Self;
 }
 
+// Applies a whole chain of transforms in one go, compose them with Affine::then first.
+pub trait EvalTransform: Evaluate {
+    fn transform(&self, affine: &Affine) -> Self;
+}
+
 impl<T: Evaluate + Send + Sync> EvalTranslate for T {
     fn translate(&self, t: Vector) -> Self {
         let transform = |v: &Vector| {
@@ -62,3 +143,12 @@ impl<T: Evaluate> EvalRotate for T {
         return self.apply_transform(&transform);
     }
 }
+
+impl<T: Evaluate> EvalTransform for T {
+    fn transform(&self, affine: &Affine) -> Self {
+        let affine = *affine;
+        let transform = move |v: &Vector| affine.apply(*v);
+
+        self.apply_transform(transform)
+    }
+}
@@ -0,0 +1,101 @@
+use glifparser::glif::contour_operations::vws::CapType;
+use glifparser::JoinType;
+
+use crate::error::{MathError, MathResult};
+use crate::glyphbuilder::{fix_path, GlyphBuilder};
+use crate::reverse::Reverse;
+use crate::{Bezier, Evaluate, Piecewise, Vector};
+
+// How far the edges of the stroke may be from where they should be.
+const STROKE_TOLERANCE: f64 = 0.01;
+
+/// Strokes every contour of the outline with the same width all the way along, without needing
+/// fontforge or any VWS handles. A closed contour gives a contour on either side of it, an open
+/// one a single contour around both sides with caps on the ends. Square caps end the stroke flat
+/// at the ends of the contour the way they do in VWS. Where the stroke is wider than a curve is
+/// round the inside edge loops back on itself, run remove_overlaps on the result to clean that up.
+pub fn expand_stroke(
+    outline: &Piecewise<Piecewise<Bezier>>,
+    width: f64,
+    join: JoinType,
+    cap: CapType,
+) -> Piecewise<Piecewise<Bezier>> {
+    try_expand_stroke(outline, width, join, cap)
+        .unwrap_or_else(|err| panic!("Couldn't stroke the outline: {}", err))
+}
+
+// Fallible version of expand_stroke. Custom caps need a cap glyph which only VWS can be given, so
+// asking for one here is a MissingCap error.
+pub fn try_expand_stroke(
+    outline: &Piecewise<Piecewise<Bezier>>,
+    width: f64,
+    join: JoinType,
+    cap: CapType,
+) -> MathResult<Piecewise<Piecewise<Bezier>>> {
+    let mut output = Vec::new();
+
+    for (i, contour) in outline.segs.iter().enumerate() {
+        if contour.segs.is_empty() {
+            continue;
+        }
+
+        let stroked =
+            stroke_contour(contour, width / 2., join, &cap).map_err(MathError::in_segment(i))?;
+        output.extend(stroked);
+    }
+
+    Ok(Piecewise::new(output, None))
+}
+
+fn stroke_contour(
+    contour: &Piecewise<Bezier>,
+    distance: f64,
+    join: JoinType,
+    cap: &CapType,
+) -> MathResult<Vec<Piecewise<Bezier>>> {
+    let closed = contour.is_closed();
+
+    // both sides run along the right of the direction they're traced in, so the left side is the
+    // offset of the reversed contour
+    let right = GlyphBuilder {
+        beziers: contour.offset(distance, STROKE_TOLERANCE).segs,
+    };
+    let left = GlyphBuilder {
+        beziers: contour.reverse().offset(distance, STROKE_TOLERANCE).segs,
+    };
+    let right = fix_path(right, closed, join);
+    let left = fix_path(left, closed, join);
+
+    if closed {
+        return Ok(vec![
+            Piecewise::new(right.beziers, None),
+            Piecewise::new(left.beziers, None),
+        ]);
+    }
+
+    let left_start = left.beziers.first().unwrap().start_point();
+    let right_start = right.beziers.first().unwrap().start_point();
+
+    let mut out = right;
+    cap_to(&mut out, left_start, cap)?;
+    out.append(left);
+    cap_to(&mut out, right_start, cap)?;
+
+    Ok(vec![Piecewise::new(out.beziers, None)])
+}
+
+// Caps the stroke from the end of one side across to the other. The contour is traced counter
+// clockwise, so the way out of the end of the stroke is to the right of the way across it.
+fn cap_to(builder: &mut GlyphBuilder, to: Vector, cap: &CapType) -> MathResult<()> {
+    let from = builder.beziers.last().unwrap().end_point();
+    let outwards = (to - from).right_normal();
+
+    match cap {
+        CapType::Round => builder.arc_to(to, outwards, -outwards),
+        CapType::Circle => builder.circle_arc_to(to, outwards, -outwards),
+        CapType::Square => builder.line_to(to),
+        CapType::Custom => return Err(MathError::MissingCap),
+    }
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
-use glifparser::Contour;
+use glifparser::{Contour, Point, PointData};
+
+// The first and second control points of the curves between the knots.
+type ControlPoints = (Vec<(f32, f32)>, Vec<(f32, f32)>);
+
 // https://www.codeproject.com/Articles/31859/Draw-a-Smooth-Curve-through-a-Set-of-2D-Points-wit
 
-pub fn get_curve_control_point(knots: Contour<()>) -> (Vec<(f32, f32)>, Vec<(f32, f32)>) {
+pub fn get_curve_control_point<PD: PointData>(knots: Contour<PD>) -> ControlPoints {
     let n = knots.len() - 1;
 
     let mut first_control_point: Vec<(f32, f32)> = Vec::new();
@@ -74,3 +78,76 @@ fn get_first_control_points(rhs: Vec<f32>) -> Vec<f32> {
     }
     x
 }
+
+// The same smooth curve through closed knots, where the last knot joins back up to the first.
+// Every knot gets a first control point for the curve leaving it, and the second control points
+// are for the curves arriving at knots 1, 2, ... n - 1 and finally 0. Needs at least 3 knots.
+pub fn get_closed_curve_control_points<PD: PointData>(knots: &[Point<PD>]) -> ControlPoints {
+    let n = knots.len();
+
+    // Every knot has the same equation, P1[i - 1] + 4 * P1[i] + P1[i + 1] = 4 * K[i] + 2 * K[i + 1],
+    // with the indices wrapping around.
+    let x = get_first_control_points_closed(
+        (0..n)
+            .map(|i| 4. * knots[i].x + 2. * knots[(i + 1) % n].x)
+            .collect(),
+    );
+    let y = get_first_control_points_closed(
+        (0..n)
+            .map(|i| 4. * knots[i].y + 2. * knots[(i + 1) % n].y)
+            .collect(),
+    );
+
+    let first_control_point = x.iter().copied().zip(y.iter().copied()).collect();
+    // P2[i] = 2 * K[i + 1] - P1[i + 1]
+    let second_control_point = (0..n)
+        .map(|i| {
+            let next = (i + 1) % n;
+            (2. * knots[next].x - x[next], 2. * knots[next].y - y[next])
+        })
+        .collect();
+
+    (first_control_point, second_control_point)
+}
+
+// Solves the cyclic tridiagonal system with 4 on the diagonal and 1 everywhere next to it, the
+// corners included. The corners are taken out with the Sherman-Morrison formula, leaving two
+// ordinary tridiagonal systems.
+fn get_first_control_points_closed(rhs: Vec<f32>) -> Vec<f32> {
+    let n = rhs.len();
+    let gamma = -4.;
+
+    let mut diagonal = vec![4.; n];
+    diagonal[0] -= gamma;
+    diagonal[n - 1] -= 1. / gamma;
+
+    let mut u = vec![0.; n];
+    u[0] = gamma;
+    u[n - 1] = 1.;
+
+    let mut x = solve_tridiagonal(&diagonal, rhs);
+    let z = solve_tridiagonal(&diagonal, u);
+
+    let fact = (x[0] + x[n - 1] / gamma) / (1. + z[0] + z[n - 1] / gamma);
+    x.iter_mut().zip(z).for_each(|(x, z)| *x -= fact * z);
+    x
+}
+
+// Tridiagonal solve with 1 next to the diagonal.
+fn solve_tridiagonal(diagonal: &[f32], rhs: Vec<f32>) -> Vec<f32> {
+    let n = rhs.len();
+    let mut x = rhs;
+    let mut tmp = vec![0.; n];
+    let mut b = diagonal[0];
+    x[0] /= b;
+    for i in 1..n {
+        //decomposition and forward substitution
+        tmp[i] = 1. / b;
+        b = diagonal[i] - tmp[i];
+        x[i] = (x[i] - x[i - 1]) / b;
+    }
+    for i in 1..n {
+        x[n - i - 1] -= tmp[n - i] * x[n - i]; // Backsubstitution.
+    }
+    x
+}
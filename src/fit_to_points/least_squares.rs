@@ -0,0 +1,110 @@
+use crate::{Bezier, Vector};
+
+/// Least squares fit of a cubic to points with known t values, after Schneider's "An Algorithm for
+/// Automatically Fitting Digitized Curves". The cubic runs from the first point to the last one,
+/// leaving in the direction of start_tangent and arriving in the direction of end_tangent, only
+/// the lengths of its handles are fitted. Where the best fit would need a handle to point backwards
+/// both handles are set to a third of the chord instead.
+pub fn fit_cubic_with_tangents(
+    points: &[Vector],
+    ts: &[f64],
+    start_tangent: Vector,
+    end_tangent: Vector,
+) -> Bezier {
+    let first = points[0];
+    let last = points[points.len() - 1];
+    let chord = first.distance(last);
+
+    let direction = |tangent: Vector| {
+        if tangent.magnitude() > 0. {
+            tangent.normalize()
+        } else {
+            (last - first).normalize()
+        }
+    };
+    let start_direction = direction(start_tangent);
+    let end_direction = -direction(end_tangent);
+
+    // the normal equations of the two handle lengths
+    let mut c = [[0.; 2]; 2];
+    let mut x = [0.; 2];
+    for (point, t) in points.iter().zip(ts) {
+        let mt = 1. - t;
+        let b0 = mt * mt * mt;
+        let b1 = 3. * t * mt * mt;
+        let b2 = 3. * t * t * mt;
+        let b3 = t * t * t;
+
+        let a1 = start_direction * b1;
+        let a2 = end_direction * b2;
+        let rest = *point - (first * (b0 + b1) + last * (b2 + b3));
+
+        c[0][0] += a1.dot(a1);
+        c[0][1] += a1.dot(a2);
+        c[1][1] += a2.dot(a2);
+        x[0] += a1.dot(rest);
+        x[1] += a2.dot(rest);
+    }
+
+    let determinant = c[0][0] * c[1][1] - c[0][1] * c[0][1];
+    let (mut alpha1, mut alpha2) = if determinant.abs() > f64::EPSILON {
+        (
+            (x[0] * c[1][1] - x[1] * c[0][1]) / determinant,
+            (c[0][0] * x[1] - c[0][1] * x[0]) / determinant,
+        )
+    } else {
+        (chord / 3., chord / 3.)
+    };
+
+    let min_alpha = chord * 1e-6;
+    if alpha1 < min_alpha || alpha2 < min_alpha {
+        alpha1 = chord / 3.;
+        alpha2 = chord / 3.;
+    }
+
+    Bezier::from_points(
+        first,
+        first + start_direction * alpha1,
+        last + end_direction * alpha2,
+        last,
+    )
+}
+
+/// One step of Newton's method on each t value, moving it towards the t of the point on the curve
+/// that's closest to the point it belongs to.
+pub(crate) fn reparameterize(bez: &Bezier, points: &[Vector], ts: &[f64]) -> Vec<f64> {
+    let [a, b, c, d] = bez.power_coefficients();
+
+    points
+        .iter()
+        .zip(ts)
+        .map(|(point, &t)| {
+            let difference = ((a * t + b) * t + c) * t + d - *point;
+            let first = (a * 3. * t + b * 2.) * t + c;
+            let second = a * 6. * t + b * 2.;
+
+            let numerator = difference.dot(first);
+            let denominator = first.dot(first) + difference.dot(second);
+            if denominator.abs() < f64::EPSILON {
+                return t;
+            }
+
+            (t - numerator / denominator).clamp(0., 1.)
+        })
+        .collect()
+}
+
+// t values spaced by the distances between the points.
+pub(crate) fn chord_length_parameters(points: &[Vector]) -> Vec<f64> {
+    let mut ts = vec![0.];
+    for pair in points.windows(2) {
+        ts.push(ts[ts.len() - 1] + pair[0].distance(pair[1]));
+    }
+
+    let total = ts[ts.len() - 1];
+    if total > 0. {
+        ts.iter_mut().for_each(|t| *t /= total);
+    }
+
+    ts
+}
@@ -1,11 +1,21 @@
 mod get_control_points;
-use glifparser::{Contour, Handle, Outline, PointType};
-pub fn fit(outline: Outline<()>) -> Outline<()> {
-    let mut result_outline: Outline<()> = Vec::new();
+pub(crate) mod least_squares;
+mod schneider;
+
+pub use self::least_squares::fit_cubic_with_tangents;
+pub use self::schneider::fit_curve;
+
+use glifparser::{Contour, Handle, Outline, Point, PointData, PointType};
+
+/// Gives the curve points of every contour handles that make the contour as smooth as it can be
+/// through them. Closed contours made only of curve points are smooth all the way around, start
+/// point included.
+pub fn fit<PD: PointData>(outline: Outline<PD>) -> Outline<PD> {
+    let mut result_outline: Outline<PD> = Vec::new();
     for contour in outline.iter() {
-        let mut final_contour: Contour<()> = Vec::new();
+        let mut final_contour: Contour<PD> = Vec::new();
         let mut last_ptype = PointType::Undefined;
-        let mut curve_contour: Contour<()> = Vec::new();
+        let mut curve_contour: Contour<PD> = Vec::new();
         for point in contour.iter() {
             if point.ptype == PointType::Curve {
                 if last_ptype == PointType::Curve {
@@ -26,7 +36,7 @@ pub fn fit(outline: Outline<()>) -> Outline<()> {
             last_ptype = point.ptype;
         }
         if final_contour.is_empty() {
-            final_contour = solve(contour.clone());
+            final_contour = solve_closed(contour.clone());
         } else if final_contour.len() != contour.len() {
             final_contour.append(&mut solve(curve_contour.clone()));
         }
@@ -35,7 +45,7 @@ pub fn fit(outline: Outline<()>) -> Outline<()> {
     result_outline
 }
 
-fn solve(contour: Vec<glifparser::Point<()>>) -> Vec<glifparser::Point<()>> {
+fn solve<PD: PointData>(contour: Vec<Point<PD>>) -> Vec<Point<PD>> {
     if contour.len() == 1 {
         return contour;
     }
@@ -60,3 +70,20 @@ fn solve(contour: Vec<glifparser::Point<()>>) -> Vec<glifparser::Point<()>> {
     }
     contour
 }
+
+// Every point is a curve point, so the contour is closed and the curve out of the last point goes
+// back to the first.
+fn solve_closed<PD: PointData>(contour: Vec<Point<PD>>) -> Vec<Point<PD>> {
+    if contour.len() < 3 {
+        return solve(contour);
+    }
+
+    let (first, second) = get_control_points::get_closed_curve_control_points(&contour);
+    let n = contour.len();
+    let mut contour = contour;
+    for (i, (a, b)) in first.iter().zip(&second).enumerate() {
+        contour[i].a = Handle::At(a.0, a.1);
+        contour[(i + 1) % n].b = Handle::At(b.0, b.1);
+    }
+    contour
+}
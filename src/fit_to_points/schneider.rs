@@ -0,0 +1,164 @@
+use super::least_squares::{chord_length_parameters, fit_cubic_with_tangents, reparameterize};
+use crate::consts::SMALL_DISTANCE;
+use crate::{Bezier, Evaluate, Piecewise, Vector};
+
+// Rounds of reparameterizing before we give up and split.
+const MAX_ITERATIONS: usize = 20;
+
+// Reparameterizing stops once a round no longer takes at least this fraction off the error.
+const MIN_IMPROVEMENT: f64 = 0.01;
+
+/// Fits as few cubics as it takes to pass within tolerance of every one of the points, using the
+/// algorithm from Schneider's "An Algorithm for Automatically Fitting Digitized Curves". The path
+/// gets a corner wherever the points turn by more than corner_angle, in radians, and is smooth
+/// everywhere else. If the last point is the first one again the path is closed, and it only has
+/// a corner there if the points turn sharply enough there too.
+pub fn fit_curve(points: &[Vector], tolerance: f64, corner_angle: f64) -> Piecewise<Bezier> {
+    let mut points = points.to_vec();
+    points.dedup_by(|b, a| b.is_near(*a, SMALL_DISTANCE));
+    if points.len() < 2 {
+        return Piecewise::new(Vec::new(), None);
+    }
+
+    let last = points.len() - 1;
+    let closed = last > 1 && points[0].is_near(points[last], SMALL_DISTANCE);
+
+    let turns_sharply = |before: Vector, at: Vector, after: Vector| {
+        let (incoming, outgoing) = (at - before, after - at);
+        f64::atan2(incoming.cross(outgoing), incoming.dot(outgoing)).abs() > corner_angle
+    };
+
+    let mut corners = vec![0];
+    corners.extend((1..last).filter(|&i| turns_sharply(points[i - 1], points[i], points[i + 1])));
+    corners.push(last);
+
+    // a smooth closed path leaves its start in the direction it arrives in
+    let seam_tangent = if closed && !turns_sharply(points[last - 1], points[0], points[1]) {
+        Some(center_tangent(points[last - 1], points[0], points[1]))
+    } else {
+        None
+    };
+
+    let mut segs = Vec::new();
+    for pair in corners.windows(2) {
+        let run = &points[pair[0]..=pair[1]];
+        let start_tangent = match seam_tangent {
+            Some(tangent) if pair[0] == 0 => tangent,
+            _ => tangent_at_start(run),
+        };
+        let end_tangent = match seam_tangent {
+            Some(tangent) if pair[1] == last => tangent,
+            _ => tangent_at_end(run),
+        };
+
+        fit_run(run, start_tangent, end_tangent, tolerance, &mut segs);
+    }
+
+    Piecewise::new(segs, None)
+}
+
+fn fit_run(
+    points: &[Vector],
+    start_tangent: Vector,
+    end_tangent: Vector,
+    tolerance: f64,
+    output: &mut Vec<Bezier>,
+) {
+    let (first, last) = (points[0], points[points.len() - 1]);
+    if points.len() == 2 {
+        let third = first.distance(last) / 3.;
+        output.push(Bezier::from_points(
+            first,
+            first + start_tangent.normalize() * third,
+            last - end_tangent.normalize() * third,
+            last,
+        ));
+        return;
+    }
+
+    let mut ts = chord_length_parameters(points);
+    let mut bez = fit_cubic_with_tangents(points, &ts, start_tangent, end_tangent);
+    let (mut error, mut split) = max_error(&bez, points, &ts);
+
+    for _ in 0..MAX_ITERATIONS {
+        if error <= tolerance {
+            output.push(bez);
+            return;
+        }
+
+        let new_ts = reparameterize(&bez, points, &ts);
+        let new_bez = fit_cubic_with_tangents(points, &new_ts, start_tangent, end_tangent);
+        let (new_error, new_split) = max_error(&new_bez, points, &new_ts);
+        if new_error > error * (1. - MIN_IMPROVEMENT) {
+            break;
+        }
+
+        ts = new_ts;
+        bez = new_bez;
+        error = new_error;
+        split = new_split;
+    }
+
+    if error <= tolerance {
+        output.push(bez);
+        return;
+    }
+
+    // split where the fit is worst and keep the path smooth there
+    let split_tangent = center_tangent(points[split - 1], points[split], points[split + 1]);
+    fit_run(
+        &points[..=split],
+        start_tangent,
+        split_tangent,
+        tolerance,
+        output,
+    );
+    fit_run(
+        &points[split..],
+        split_tangent,
+        end_tangent,
+        tolerance,
+        output,
+    );
+}
+
+// The largest distance between a point and where the curve is at its t value, and the index of
+// that point. Only the inner points are considered, the ends are always hit exactly.
+fn max_error(bez: &Bezier, points: &[Vector], ts: &[f64]) -> (f64, usize) {
+    let mut worst = (0., points.len() / 2);
+
+    for i in 1..points.len() - 1 {
+        let error = bez.at(ts[i]).distance(points[i]);
+        if error > worst.0 {
+            worst = (error, i);
+        }
+    }
+
+    worst
+}
+
+// Tangents come from the parabola through three neighbouring points, with the points spaced by
+// their distances along it. That's much closer than the direction from one point to the next
+// wherever the points are curving.
+fn center_tangent(before: Vector, at: Vector, after: Vector) -> Vector {
+    let (d1, d2) = (before.distance(at), at.distance(after));
+    before * (-d2 / (d1 * (d1 + d2)))
+        + at * ((d2 - d1) / (d1 * d2))
+        + after * (d1 / (d2 * (d1 + d2)))
+}
+
+fn tangent_at_start(points: &[Vector]) -> Vector {
+    if points.len() < 3 {
+        return points[1] - points[0];
+    }
+
+    let (start, next, after) = (points[0], points[1], points[2]);
+    let (d1, d2) = (start.distance(next), next.distance(after));
+    start * (-(2. * d1 + d2) / (d1 * (d1 + d2))) + next * ((d1 + d2) / (d1 * d2))
+        - after * (d1 / (d2 * (d1 + d2)))
+}
+
+fn tangent_at_end(points: &[Vector]) -> Vector {
+    let reversed: Vec<_> = points.iter().rev().take(3).copied().collect();
+    -tangent_at_start(&reversed)
+}
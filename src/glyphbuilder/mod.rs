@@ -1,12 +1,13 @@
 use std::f64::consts;
 
-use glifparser::Glif;
+use glifparser::{Glif, JoinType};
 
 use flo_curves::line::line_intersects_line;
 
+use crate::ellipticalarc::EllipticalArc;
 use crate::subdivide::Subdivide;
 use crate::{consts::SMALL_DISTANCE, vec2};
-use crate::{Bezier, EvalRotate, EvalScale, EvalTranslate, Evaluate, Piecewise, Vector};
+use crate::{Affine, Bezier, EvalTransform, Evaluate, Piecewise, Vector};
 
 #[allow(unused)]
 fn vec2_to_rad(vec: Vector) -> f64 {
@@ -99,16 +100,8 @@ impl GlyphBuilder {
         let angle = f64::acos(dot_product);
 
         let n = f64::abs(consts::TAU / angle);
-        let tangent1_right = Vector {
-            x: tangent1.y,
-            y: -tangent1.x,
-        }
-        .normalize();
-        let tangent2_right = Vector {
-            x: tangent2.y,
-            y: -tangent2.x,
-        }
-        .normalize();
+        let tangent1_right = tangent1.right_normal();
+        let tangent2_right = tangent2.right_normal();
 
         let line1 = (from, from + tangent1_right * 2048.);
         let line2 = (to, to + tangent2_right * 2048.);
@@ -152,16 +145,8 @@ impl GlyphBuilder {
     pub fn circle_arc_to(&mut self, to: Vector, tangent1: Vector, tangent2: Vector) {
         let from = self.beziers.last().unwrap().end_point();
 
-        let tangent1_right = Vector {
-            x: tangent1.y,
-            y: -tangent1.x,
-        }
-        .normalize();
-        let tangent2_right = Vector {
-            x: tangent2.y,
-            y: -tangent2.x,
-        }
-        .normalize();
+        let tangent1_right = tangent1.right_normal();
+        let tangent2_right = tangent2.right_normal();
 
         let ray1 = (from, from + tangent1_right * 2048.);
         let ray2 = (to, to + tangent2_right * 2048.);
@@ -265,6 +250,34 @@ impl GlyphBuilder {
         self.bezier_to(circle_segment);
     }
 
+    /// Draws an elliptical arc to to the way an SVG path's arc command does, see
+    /// [`EllipticalArc::from_svg`]. x_rotation is in degrees. Like in SVG a zero radius draws a
+    /// straight line instead.
+    pub fn svg_arc_to(
+        &mut self,
+        rx: f64,
+        ry: f64,
+        x_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        to: Vector,
+    ) {
+        let from = self.beziers.last().unwrap().end_point();
+
+        match EllipticalArc::from_svg(from, rx, ry, x_rotation, large_arc, sweep, to) {
+            Some(arc) => {
+                let mut cubics = arc.to_cubics(SMALL_DISTANCE);
+
+                // the arc's end points come out of sin and cos, make sure we connect exactly
+                cubics.first_mut().unwrap().w1 = from;
+                cubics.last_mut().unwrap().w4 = to;
+                self.append_vec(cubics);
+            }
+            None if from != to => self.line_to(to),
+            None => {}
+        }
+    }
+
     pub fn cap_to<T: glifparser::PointData>(&mut self, to: Vector, cap: &Glif<T>) {
         let cap_pw = Piecewise::from(cap.outline.as_ref().unwrap().first().unwrap());
         let from = self.beziers.last().unwrap().end_point();
@@ -276,21 +289,17 @@ impl GlyphBuilder {
         // get the distance from -> to and use that to scale the cap
         let goal_size = from.distance(to);
         let cur_size = cap_first_point.distance(cap_last_point);
-        let scaled_cap = cap_pw.scale(vec2!(goal_size / cur_size, goal_size / cur_size));
+        let scale = goal_size / cur_size;
 
         // we need to center the cap at the point between the first point in the contour and the last
-        let s_cap_first_point = scaled_cap.segs.first().unwrap().start_point();
-        let s_cap_last_point = scaled_cap.segs.last().unwrap().end_point();
-        let cap_mid_point = s_cap_first_point.lerp(s_cap_last_point, 0.5);
-        // we translate by -mid_point which brings the cap's midpoint to origin
-        let translated_cap = scaled_cap.translate(vec2!(-cap_mid_point.x, -cap_mid_point.y));
+        let cap_mid_point = cap_first_point.lerp(cap_last_point, 0.5);
 
         // then we rotate the cap into position by getting the angle between +1, 0 and the normalized tangent
         // of the line between from->to and rotating it
         let tangent = from - to;
-        let cap_tangent = s_cap_first_point - s_cap_last_point;
-        let normal = vec2!(tangent.y, -tangent.x).normalize();
-        let cap_normal = vec2!(cap_tangent.y, -cap_tangent.x).normalize();
+        let cap_tangent = cap_first_point - cap_last_point;
+        let normal = tangent.right_normal();
+        let cap_normal = cap_tangent.right_normal();
 
         let dot_product = normal.dot(-cap_normal);
         let angle = {
@@ -302,11 +311,13 @@ impl GlyphBuilder {
             }
         };
 
-        let rotated_cap = translated_cap.rotate(angle);
-
-        // finally we translate the cap from 0,0 being the midpoint between the caps first and last points
-        // to the mid point between from and to moving from 'cap space' to 'world space'
-        let final_cap = rotated_cap.translate(join_mid_point);
+        // we bring the cap's midpoint to the origin, scale and rotate it there and finally move it to
+        // the mid point between from and to, moving from 'cap space' to 'world space'
+        let placement = Affine::translate(-cap_mid_point)
+            .then(Affine::scale(vec2!(scale, scale)))
+            .then(Affine::rotate(angle))
+            .then(Affine::translate(join_mid_point));
+        let final_cap = cap_pw.transform(&placement);
 
         for bezier in final_cap.segs.iter().rev() {
             self.bezier_to(bezier.reverse());
@@ -351,3 +362,75 @@ impl GlyphBuilder {
         };
     }
 }
+
+// takes a vector of beziers and fills in discontinuities with joins
+pub(crate) fn fix_path(in_path: GlyphBuilder, closed: bool, join_type: JoinType) -> GlyphBuilder {
+    let mut out = GlyphBuilder::new();
+
+    let join_to = match join_type {
+        JoinType::Bevel => GlyphBuilder::bevel_to,
+        JoinType::Round => GlyphBuilder::arc_to,
+        JoinType::Circle => GlyphBuilder::circle_arc_to,
+        JoinType::Miter => GlyphBuilder::miter_to,
+    };
+
+    let mut path_iter = in_path.beziers.iter().peekable();
+
+    while let Some(bezier) = path_iter.next() {
+        if let Some(next_bezier) = path_iter.peek() {
+            let next_start = next_bezier.start_point();
+            let last_end = bezier.end_point();
+            if !last_end.is_near(next_start, SMALL_DISTANCE) {
+                // the end of our last curve doesn't match up with the start of our next so we need to
+                // deal with the discontinuity be creating a join
+                let from_end_point = bezier.at(1.);
+                let to_start_point = next_bezier.at(0.);
+
+                // used for round joins
+                let tangent1 = bezier.tangent_at(1.).normalize();
+                let tangent2 = next_bezier.tangent_at(0.).normalize();
+
+                let discontinuity_vec = to_start_point - from_end_point;
+                let dr = discontinuity_vec.right_normal();
+
+                let t1_dot_dr = tangent1.dot(dr);
+                let t2_dot_dr = tangent2.dot(dr);
+                let tangent1 = if t1_dot_dr > 0. { tangent1 } else { -tangent1 };
+                let tangent2 = if t2_dot_dr < 0. { tangent2 } else { -tangent2 };
+
+                out.bezier_to(bezier.clone());
+                join_to(&mut out, next_start, tangent1, tangent2);
+            } else {
+                out.bezier_to(bezier.clone());
+            }
+        } else if closed {
+            // our path is closed and if there's not a next point we need to make sure that our current
+            // and last curve matches up with the first one
+
+            let first_bez = in_path.beziers.first().unwrap();
+            let first_point = first_bez.start_point();
+            let last_end = bezier.end_point();
+
+            if !last_end.is_near(first_point, SMALL_DISTANCE) {
+                let tangent1 = bezier.tangent_at(1.).normalize();
+                let tangent2 = first_bez.tangent_at(0.).normalize();
+                let discontinuity_vec = first_point - last_end;
+                let on_outside = Vector::dot(tangent2, discontinuity_vec) <= 0.;
+
+                if !on_outside {
+                    out.bezier_to(bezier.clone());
+                    join_to(&mut out, first_point, tangent1, tangent2);
+                } else {
+                    out.bezier_to(bezier.clone());
+                    out.line_to(first_point);
+                }
+            } else {
+                out.bezier_to(bezier.clone());
+            }
+        } else {
+            out.bezier_to(bezier.clone());
+        }
+    }
+
+    return out;
+}
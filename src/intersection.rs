@@ -0,0 +1,530 @@
+use crate::consts::{CLOSE_DISTANCE, SMALL_DISTANCE, SMALL_T_DISTANCE};
+use crate::quadbezier::QuadBezier;
+use crate::roots::{solve_cubic, unit_interval_roots};
+use crate::subdivide::Subdivide;
+use crate::{Bezier, Evaluate, Piecewise, Rect, Vector};
+
+// Control polygons that deviate from their chord by less than this are treated as line segments
+// when we bottom out of the subdivision.
+const FLATNESS: f64 = 0.0001;
+
+// Hard limit on how deep we subdivide, this only matters for degenerate input.
+const MAX_DEPTH: usize = 32;
+
+// Two curves running within this distance of each other are considered to be touching.
+const OVERLAP_DISTANCE: f64 = SMALL_DISTANCE;
+
+// Touching curves that are further apart than this anywhere inside the contact are tangent to
+// each other rather than overlapping.
+const COINCIDENT_DISTANCE: f64 = 0.000001;
+
+// Intersections closer than this in both t values are the same intersection.
+const SAME_T_DISTANCE: f64 = 0.0001;
+
+/// An intersection is reported as (t on self, t on other, point). Results are sorted by the first
+/// t value. Curves that touch tangentially yield a single intersection where they are closest, and
+/// curves that overlap along a stretch yield the two ends of the overlap.
+pub trait Intersect<Rhs: ?Sized = Self> {
+    fn intersections(&self, other: &Rhs) -> Vec<(f64, f64, Vector)>;
+}
+
+impl Intersect for Bezier {
+    fn intersections(&self, other: &Bezier) -> Vec<(f64, f64, Vector)> {
+        cubic_cubic_intersections(self, other)
+    }
+}
+
+impl Intersect<QuadBezier> for Bezier {
+    fn intersections(&self, other: &QuadBezier) -> Vec<(f64, f64, Vector)> {
+        cubic_cubic_intersections(self, &other.to_cubic())
+    }
+}
+
+impl Intersect<Bezier> for QuadBezier {
+    fn intersections(&self, other: &Bezier) -> Vec<(f64, f64, Vector)> {
+        cubic_cubic_intersections(&self.to_cubic(), other)
+    }
+}
+
+impl Intersect for QuadBezier {
+    fn intersections(&self, other: &QuadBezier) -> Vec<(f64, f64, Vector)> {
+        cubic_cubic_intersections(&self.to_cubic(), &other.to_cubic())
+    }
+}
+
+// A line is given as a (start, end) pair like in flo_curves. The second t value is the position
+// along the line segment.
+impl Intersect<(Vector, Vector)> for Bezier {
+    fn intersections(&self, line: &(Vector, Vector)) -> Vec<(f64, f64, Vector)> {
+        cubic_line_intersections(self, line, true)
+    }
+}
+
+impl Intersect<(Vector, Vector)> for QuadBezier {
+    fn intersections(&self, line: &(Vector, Vector)) -> Vec<(f64, f64, Vector)> {
+        cubic_line_intersections(&self.to_cubic(), line, true)
+    }
+}
+
+// The t values reported for a piecewise are global t values, so they respect the cuts.
+impl<T, U> Intersect<Piecewise<U>> for Piecewise<T>
+where
+    T: Evaluate + Intersect<U>,
+    U: Evaluate,
+{
+    fn intersections(&self, other: &Piecewise<U>) -> Vec<(f64, f64, Vector)> {
+        let mut output = Vec::new();
+
+        for (i, seg) in self.segs.iter().enumerate() {
+            for (j, other_seg) in other.segs.iter().enumerate() {
+                for (t_a, t_b, point) in seg.intersections(other_seg) {
+                    output.push((self.global_t(i, t_a), other.global_t(j, t_b), point));
+                }
+            }
+        }
+
+        // neighbouring segments share an end point so an intersection there shows up twice
+        dedup_intersections(output)
+    }
+}
+
+impl<T> Intersect<(Vector, Vector)> for Piecewise<T>
+where
+    T: Evaluate + Intersect<(Vector, Vector)>,
+{
+    fn intersections(&self, line: &(Vector, Vector)) -> Vec<(f64, f64, Vector)> {
+        let mut output = Vec::new();
+
+        for (i, seg) in self.segs.iter().enumerate() {
+            for (t, line_t, point) in seg.intersections(line) {
+                output.push((self.global_t(i, t), line_t, point));
+            }
+        }
+
+        dedup_intersections(output)
+    }
+}
+
+impl Bezier {
+    /// Intersects the curve with the infinite line running through both points of the ray. The
+    /// second t value is relative to the ray's points and can fall outside of 0-1.
+    pub fn ray_intersections(&self, ray: &(Vector, Vector)) -> Vec<(f64, f64, Vector)> {
+        cubic_line_intersections(self, ray, false)
+    }
+}
+
+impl QuadBezier {
+    /// See [`Bezier::ray_intersections`].
+    pub fn ray_intersections(&self, ray: &(Vector, Vector)) -> Vec<(f64, f64, Vector)> {
+        cubic_line_intersections(&self.to_cubic(), ray, false)
+    }
+}
+
+// Sorts the intersections and merges the ones that are the same intersection found twice.
+fn dedup_intersections(mut intersections: Vec<(f64, f64, Vector)>) -> Vec<(f64, f64, Vector)> {
+    intersections.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+
+    let mut output: Vec<(f64, f64, Vector)> = Vec::new();
+    for intersection in intersections {
+        let duplicate = output.iter().any(|kept| {
+            f64::abs(kept.0 - intersection.0) <= SAME_T_DISTANCE
+                && f64::abs(kept.1 - intersection.1) <= SAME_T_DISTANCE
+        });
+
+        if !duplicate {
+            output.push(intersection);
+        }
+    }
+
+    output
+}
+
+fn cubic_line_intersections(
+    bez: &Bezier,
+    line: &(Vector, Vector),
+    bounded: bool,
+) -> Vec<(f64, f64, Vector)> {
+    let (line_start, line_end) = *line;
+    let direction = line_end - line_start;
+    let length_squared = direction.dot(direction);
+    if length_squared == 0. {
+        return vec![];
+    }
+
+    let normal = Vector {
+        x: -direction.y,
+        y: direction.x,
+    };
+    let line_t = |p: Vector| (p - line_start).dot(direction) / length_squared;
+    let on_line = |t: f64| !bounded || (-SMALL_T_DISTANCE..=1. + SMALL_T_DISTANCE).contains(&t);
+    let clamp_line_t = |t: f64| if bounded { t.clamp(0., 1.) } else { t };
+
+    let [a, b, c, d] = bez.power_coefficients();
+    let mut output = Vec::new();
+
+    let collinear = bez.to_control_points().iter().all(|p| {
+        f64::abs((*p - line_start).dot(normal)) <= OVERLAP_DISTANCE * length_squared.sqrt()
+    });
+
+    if collinear {
+        // The whole curve lies on the line so every point is an intersection. We report the ends
+        // of the overlap: the curve's end points that are on the line segment and the line
+        // segment's end points that are on the curve.
+        for t in [0., 1.] {
+            let point = bez.at(t);
+            let lt = line_t(point);
+            if on_line(lt) {
+                output.push((t, clamp_line_t(lt), point));
+            }
+        }
+
+        if bounded {
+            for lt in [0., 1.] {
+                let roots = solve_cubic(
+                    a.dot(direction),
+                    b.dot(direction),
+                    c.dot(direction),
+                    (d - line_start).dot(direction) - lt * length_squared,
+                );
+                for t in unit_interval_roots(roots, SMALL_T_DISTANCE) {
+                    output.push((t, lt, bez.at(t)));
+                }
+            }
+        }
+
+        return dedup_intersections(output);
+    }
+
+    // The signed distance from the line is a cubic in t, its roots are our intersections.
+    let roots = solve_cubic(
+        a.dot(normal),
+        b.dot(normal),
+        c.dot(normal),
+        (d - line_start).dot(normal),
+    );
+
+    for t in unit_interval_roots(roots, SMALL_T_DISTANCE) {
+        let point = bez.at(t);
+        let lt = line_t(point);
+        if on_line(lt) {
+            output.push((t, clamp_line_t(lt), point));
+        }
+    }
+
+    dedup_intersections(output)
+}
+
+// A stretch of t values on both curves along which the curves touch.
+#[derive(Clone, Copy, Debug)]
+struct Overlap {
+    a: (f64, f64),
+    b: (f64, f64),
+}
+
+// Bezier clipping by bounding boxes: we keep halving both curves and throw away any pair of pieces
+// whose control polygons' bounds don't touch. Once both pieces are flat we intersect their chords
+// and polish the result with newton's method on the actual curves.
+fn cubic_cubic_intersections(a: &Bezier, b: &Bezier) -> Vec<(f64, f64, Vector)> {
+    let mut candidates = Vec::new();
+    let mut overlaps = Vec::new();
+    subdivide_pair(a, (0., 1.), b, (0., 1.), 0, &mut candidates, &mut overlaps);
+
+    let overlaps = merge_overlaps(a, overlaps);
+
+    // pieces along the edge of an overlap can fail the overlap test and get intersected as
+    // chords, those hits are already covered by the overlap
+    let mut output: Vec<(f64, f64, Vector)> = candidates
+        .into_iter()
+        .filter_map(|(t_a, t_b)| refine_intersection(a, b, t_a, t_b))
+        .filter(|(t_a, _, _)| {
+            !overlaps.iter().any(|overlap| {
+                *t_a >= overlap.a.0 - SAME_T_DISTANCE && *t_a <= overlap.a.1 + SAME_T_DISTANCE
+            })
+        })
+        .collect();
+
+    output.extend(resolve_overlaps(a, b, overlaps));
+
+    dedup_intersections(output)
+}
+
+fn lerp_range(range: (f64, f64), t: f64) -> f64 {
+    range.0 + (range.1 - range.0) * t
+}
+
+fn boxes_touch(a: &Rect, b: &Rect) -> bool {
+    a.left <= b.right + OVERLAP_DISTANCE
+        && b.left <= a.right + OVERLAP_DISTANCE
+        && a.bottom <= b.top + OVERLAP_DISTANCE
+        && b.bottom <= a.top + OVERLAP_DISTANCE
+}
+
+// How far the handles stray from the chord.
+fn flatness(bez: &Bezier) -> f64 {
+    let chord = bez.w4 - bez.w1;
+    let length = chord.magnitude();
+
+    if length < f64::EPSILON {
+        return f64::max(bez.w1.distance(bez.w2), bez.w1.distance(bez.w3));
+    }
+
+    f64::max(
+        f64::abs(chord.cross(bez.w2 - bez.w1)) / length,
+        f64::abs(chord.cross(bez.w3 - bez.w1)) / length,
+    )
+}
+
+fn halves(bez: &Bezier, range: (f64, f64)) -> Vec<(Bezier, (f64, f64))> {
+    let middle = lerp_range(range, 0.5);
+    let (first, second) = bez.split(0.5).unwrap();
+
+    vec![(first, (range.0, middle)), (second, (middle, range.1))]
+}
+
+fn subdivide_pair(
+    a: &Bezier,
+    a_range: (f64, f64),
+    b: &Bezier,
+    b_range: (f64, f64),
+    depth: usize,
+    candidates: &mut Vec<(f64, f64)>,
+    overlaps: &mut Vec<Overlap>,
+) {
+    let a_bounds = Rect::AABB_from_points(a.to_control_points_vec());
+    let b_bounds = Rect::AABB_from_points(b.to_control_points_vec());
+    if !boxes_touch(&a_bounds, &b_bounds) {
+        return;
+    }
+
+    let split_a = flatness(a) > FLATNESS;
+    let split_b = flatness(b) > FLATNESS;
+
+    if depth >= MAX_DEPTH || (!split_a && !split_b) {
+        intersect_chords(a, a_range, b, b_range, candidates, overlaps);
+        return;
+    }
+
+    let a_parts = if split_a {
+        halves(a, a_range)
+    } else {
+        vec![(a.clone(), a_range)]
+    };
+    let b_parts = if split_b {
+        halves(b, b_range)
+    } else {
+        vec![(b.clone(), b_range)]
+    };
+
+    for (a_part, a_part_range) in &a_parts {
+        for (b_part, b_part_range) in &b_parts {
+            subdivide_pair(
+                a_part,
+                *a_part_range,
+                b_part,
+                *b_part_range,
+                depth + 1,
+                candidates,
+                overlaps,
+            );
+        }
+    }
+}
+
+fn intersect_chords(
+    a: &Bezier,
+    a_range: (f64, f64),
+    b: &Bezier,
+    b_range: (f64, f64),
+    candidates: &mut Vec<(f64, f64)>,
+    overlaps: &mut Vec<Overlap>,
+) {
+    let (p0, p1) = (a.w1, a.w4);
+    let (q0, q1) = (b.w1, b.w4);
+    let da = p1 - p0;
+    let db = q1 - q0;
+
+    // a piece that has collapsed to a point, the bounds check already told us it's close enough
+    if da.dot(da) == 0. || db.dot(db) == 0. {
+        candidates.push((lerp_range(a_range, 0.5), lerp_range(b_range, 0.5)));
+        return;
+    }
+
+    // Project b's chord onto a's chord. If the part of b that lies alongside a stays within
+    // tolerance of it at both ends the two pieces run together.
+    let s0 = (q0 - p0).dot(da) / da.dot(da);
+    let s1 = (q1 - p0).dot(da) / da.dot(da);
+    if f64::abs(s1 - s0) > f64::EPSILON {
+        let low = f64::max(f64::min(s0, s1), 0.);
+        let high = f64::min(f64::max(s0, s1), 1.);
+
+        if high > low {
+            let u_low = (low - s0) / (s1 - s0);
+            let u_high = (high - s0) / (s1 - s0);
+            let low_distance = (p0 + da * low).distance(q0 + db * u_low);
+            let high_distance = (p0 + da * high).distance(q0 + db * u_high);
+
+            if low_distance <= OVERLAP_DISTANCE && high_distance <= OVERLAP_DISTANCE {
+                overlaps.push(Overlap {
+                    a: (lerp_range(a_range, low), lerp_range(a_range, high)),
+                    b: (lerp_range(b_range, u_low), lerp_range(b_range, u_high)),
+                });
+                return;
+            }
+        }
+    }
+
+    let denominator = da.cross(db);
+    if f64::abs(denominator) <= f64::EPSILON * da.magnitude() * db.magnitude() {
+        // parallel and apart
+        return;
+    }
+
+    let w = q0 - p0;
+    let s = w.cross(db) / denominator;
+    let u = w.cross(da) / denominator;
+
+    // the chords are only approximations of the curves so we're a little generous here, newton
+    // and the distance check afterwards weed out anything that doesn't hold up
+    let slack = 0.01;
+    if (-slack..=1. + slack).contains(&s) && (-slack..=1. + slack).contains(&u) {
+        candidates.push((
+            lerp_range(a_range, s.clamp(0., 1.)),
+            lerp_range(b_range, u.clamp(0., 1.)),
+        ));
+    }
+}
+
+// Newton's method on A(s) - B(t) = 0. Returns None if the candidate turns out not to be an
+// intersection after all.
+fn refine_intersection(a: &Bezier, b: &Bezier, t_a: f64, t_b: f64) -> Option<(f64, f64, Vector)> {
+    let mut s = t_a;
+    let mut t = t_b;
+
+    for _ in 0..8 {
+        let f = a.at(s) - b.at(t);
+        if f.magnitude() < f64::EPSILON {
+            break;
+        }
+
+        let da = a.tangent_at(s);
+        let db = b.tangent_at(t);
+
+        // the jacobian is [da, -db], when it's singular the curves are tangent here and newton
+        // can't help us
+        let determinant = -da.cross(db);
+        if f64::abs(determinant) <= f64::EPSILON * da.magnitude() * db.magnitude() {
+            break;
+        }
+
+        s = (s + f.cross(db) / determinant).clamp(0., 1.);
+        t = (t + f.cross(da) / determinant).clamp(0., 1.);
+    }
+
+    let point_a = a.at(s);
+    let point_b = b.at(t);
+    if point_a.distance(point_b) > SMALL_DISTANCE {
+        return None;
+    }
+
+    Some((s, t, point_a.lerp(point_b, 0.5)))
+}
+
+// Finds the t on bez closest to point, starting the search at guess.
+fn closest_t_near(bez: &Bezier, point: Vector, guess: f64) -> f64 {
+    let [a, b, c, _] = bez.power_coefficients();
+    let mut t = guess;
+
+    for _ in 0..16 {
+        let offset = bez.at(t) - point;
+        let d1 = a * (3. * t * t) + b * (2. * t) + c;
+        let d2 = a * (6. * t) + b * 2.;
+
+        let denominator = d1.dot(d1) + offset.dot(d2);
+        if f64::abs(denominator) < f64::EPSILON {
+            break;
+        }
+
+        let step = offset.dot(d1) / denominator;
+        t = (t - step).clamp(0., 1.);
+
+        if f64::abs(step) < f64::EPSILON {
+            break;
+        }
+    }
+
+    t
+}
+
+// Merges the touching stretches found by the subdivision into contiguous ones.
+fn merge_overlaps(a: &Bezier, mut overlaps: Vec<Overlap>) -> Vec<Overlap> {
+    overlaps.sort_by(|x, y| x.a.0.total_cmp(&y.a.0));
+
+    let mut merged: Vec<Overlap> = Vec::new();
+    for overlap in overlaps {
+        if let Some(last) = merged.last_mut() {
+            if overlap.a.0 <= last.a.1
+                || a.at(overlap.a.0).distance(a.at(last.a.1)) <= CLOSE_DISTANCE
+            {
+                if overlap.a.1 > last.a.1 {
+                    last.a.1 = overlap.a.1;
+                    last.b.1 = overlap.b.1;
+                }
+                continue;
+            }
+        }
+
+        merged.push(overlap);
+    }
+
+    merged
+}
+
+// Decides whether each touching stretch is a real overlap, which is reported as its two ends, or a
+// tangential contact, which is reported once.
+fn resolve_overlaps(a: &Bezier, b: &Bezier, overlaps: Vec<Overlap>) -> Vec<(f64, f64, Vector)> {
+    let mut output = Vec::new();
+    for overlap in overlaps {
+        let b_guess = |t_a: f64| {
+            let fraction = if overlap.a.1 > overlap.a.0 {
+                (t_a - overlap.a.0) / (overlap.a.1 - overlap.a.0)
+            } else {
+                0.5
+            };
+            lerp_range(overlap.b, fraction)
+        };
+        let separation = |t_a: f64| {
+            let point = a.at(t_a);
+            let t_b = closest_t_near(b, point, b_guess(t_a));
+            (point.distance(b.at(t_b)), t_b)
+        };
+
+        let coincident = [0.25, 0.5, 0.75]
+            .iter()
+            .all(|f| separation(lerp_range(overlap.a, *f)).0 <= COINCIDENT_DISTANCE);
+
+        if coincident {
+            output.push((overlap.a.0, overlap.b.0, a.at(overlap.a.0)));
+            output.push((overlap.a.1, overlap.b.1, a.at(overlap.a.1)));
+        } else {
+            // ternary search for the point of closest approach
+            let (mut low, mut high) = overlap.a;
+            for _ in 0..48 {
+                let m1 = low + (high - low) / 3.;
+                let m2 = high - (high - low) / 3.;
+                if separation(m1).0 < separation(m2).0 {
+                    high = m2;
+                } else {
+                    low = m1;
+                }
+            }
+
+            let t_a = (low + high) / 2.;
+            let (distance, t_b) = separation(t_a);
+            if distance <= SMALL_DISTANCE {
+                output.push((t_a, t_b, a.at(t_a).lerp(b.at(t_b), 0.5)));
+            }
+        }
+    }
+
+    output
+}
@@ -1,16 +1,28 @@
 #![allow(non_snake_case)] // for our name MFEKmath
+pub mod affine;
 pub mod angleparameterization;
+pub mod arclength;
 pub mod arclenparameterization;
+pub mod area;
 pub mod bezier;
+pub mod boolean;
+pub mod conic;
 pub mod consts;
 pub mod coordinate;
+pub mod curvatureparameterization;
 #[cfg(feature = "skia")]
 pub mod dash_along_path;
+pub mod ellipticalarc;
+pub mod error;
 pub mod evaluate;
+pub mod expand_stroke;
 pub mod fit_to_points;
 pub(crate) mod fixup;
 pub mod glyphbuilder;
+pub mod intersection;
+pub mod line;
 pub mod mfek;
+pub mod nearest;
 #[cfg(feature = "fontforge")]
 pub mod nib_stroking;
 pub mod parameterization;
@@ -20,9 +32,13 @@ pub mod piecewise;
 pub mod polar;
 pub mod quadbezier;
 pub mod rect;
+pub mod reverse;
+pub(crate) mod roots;
+pub mod segment;
 pub mod subdivide;
 pub mod variable_width_stroking;
 pub mod vector;
+pub mod winding;
 
 #[cfg(feature = "skia")]
 pub use {
@@ -30,15 +46,32 @@ pub use {
     skia_safe,
 };
 
+pub use self::affine::Affine;
 pub use self::angleparameterization::AngleParameterization;
-pub use self::arclenparameterization::ArcLengthParameterization;
+pub use self::arclength::ArcLength;
+pub use self::arclenparameterization::{
+    AdaptiveArcLengthParameterization, ArcLengthParameterization,
+};
+pub use self::area::{Area, AreaMoments, Orientation};
 pub use self::bezier::Bezier;
+pub use self::boolean::BooleanOp;
+pub use self::conic::Conic;
+pub use self::curvatureparameterization::CurvatureParameterization;
+pub use self::ellipticalarc::EllipticalArc;
+pub use self::error::{MathError, MathResult};
+pub use self::expand_stroke::{expand_stroke, try_expand_stroke};
 pub use self::glyphbuilder::GlyphBuilder;
+pub use self::intersection::Intersect;
+pub use self::line::Line;
+pub use self::nearest::NearestPoint;
 pub use self::parameterization::Parameterization;
 pub use self::piecewise::Piecewise;
 pub use self::rect::Rect;
+pub use self::reverse::Reverse;
+pub use self::segment::Segment;
 pub use self::vector::Vector;
+pub use self::winding::{FillRule, Winding};
 
 pub use self::evaluate::Evaluate;
-pub use self::evaluate::{EvalRotate, EvalScale, EvalTranslate};
+pub use self::evaluate::{EvalRotate, EvalScale, EvalTransform, EvalTranslate};
 pub use self::fixup::Fixup;
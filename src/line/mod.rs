@@ -0,0 +1,127 @@
+use crate::consts::SMALL_DISTANCE;
+use crate::nearest::NearestPoint;
+use crate::{subdivide::Subdivide, Bezier, Evaluate, Rect, Vector};
+
+#[derive(Clone, Debug)]
+#[allow(non_snake_case)]
+pub struct Line {
+    pub w1: Vector,
+    pub w2: Vector,
+}
+
+impl Line {
+    pub fn from_points(p0: Vector, p1: Vector) -> Self {
+        Line { w1: p0, w2: p1 }
+    }
+
+    pub fn to_control_points(&self) -> [Vector; 2] {
+        [self.w1, self.w2]
+    }
+
+    pub fn reverse(&self) -> Self {
+        Line::from_points(self.w2, self.w1)
+    }
+
+    // The handles go at the thirds so that the cubic has the same parameterization as the line.
+    pub fn to_cubic(&self) -> Bezier {
+        Bezier::from_points(
+            self.w1,
+            self.w1.lerp(self.w2, 1. / 3.),
+            self.w1.lerp(self.w2, 2. / 3.),
+            self.w2,
+        )
+    }
+
+    /// Returns the line a cubic traces if its control points all lie on the line from its start to
+    /// its end point and its handles don't stick out past them, like the lines we used to encode as
+    /// cubics with colocated handles.
+    pub fn from_cubic(bez: &Bezier) -> Option<Self> {
+        let line = Line::from_points(bez.w1, bez.w4);
+
+        let on_line = |handle: Vector| line.nearest_point(handle).2 <= SMALL_DISTANCE;
+
+        if on_line(bez.w2) && on_line(bez.w3) {
+            Some(line)
+        } else {
+            None
+        }
+    }
+}
+
+impl Evaluate for Line {
+    fn at(&self, t: f64) -> Vector {
+        self.w1.lerp(self.w2, t)
+    }
+
+    fn tangent_at(&self, _t: f64) -> Vector {
+        self.w2 - self.w1
+    }
+
+    fn second_derivative_at(&self, _t: f64) -> Vector {
+        Vector { x: 0., y: 0. }
+    }
+
+    fn bounds(&self) -> Rect {
+        Rect::AABB_from_points(vec![self.w1, self.w2])
+    }
+
+    fn apply_transform<F>(&self, transform: F) -> Self
+    where
+        F: Fn(&Vector) -> Vector,
+    {
+        Line::from_points(transform(&self.w1), transform(&self.w2))
+    }
+
+    fn start_point(&self) -> Vector {
+        self.w1
+    }
+
+    fn end_point(&self) -> Vector {
+        self.w2
+    }
+}
+
+impl Subdivide for Line {
+    fn split(&self, t: f64) -> Option<(Line, Line)> {
+        if t == 1. || t == 0. {
+            return None;
+        }
+
+        let middle = self.at(t);
+
+        Some((
+            Line::from_points(self.w1, middle),
+            Line::from_points(middle, self.w2),
+        ))
+    }
+
+    fn subsegment(&self, t0: f64, t1: f64) -> Line {
+        Line::from_points(self.at(t0), self.at(t1))
+    }
+}
+
+impl NearestPoint for Line {
+    fn nearest_point(&self, point: Vector) -> (f64, Vector, f64) {
+        let direction = self.w2 - self.w1;
+        let length_squared = direction.dot(direction);
+
+        let t = if length_squared == 0. {
+            0.
+        } else {
+            ((point - self.w1).dot(direction) / length_squared).clamp(0., 1.)
+        };
+
+        let on_line = self.at(t);
+        (t, on_line, on_line.distance(point))
+    }
+
+    // A line only ever has the one minimum.
+    fn nearest_points_within(&self, point: Vector, radius: f64) -> Vec<(f64, Vector, f64)> {
+        let nearest = self.nearest_point(point);
+        if nearest.2 > radius {
+            return vec![];
+        }
+
+        vec![nearest]
+    }
+}
@@ -11,7 +11,8 @@ use glifparser::outline::FromKurbo;
 use glifparser::{Handle, MFEKPointData, Outline, Point, PointData, PointType, WhichHandle};
 use spline::SplineSpec;
 
-use crate::{ArcLengthParameterization, Parameterization, Piecewise};
+use crate::consts::CLOSE_DISTANCE;
+use crate::{AdaptiveArcLengthParameterization, Parameterization, Piecewise};
 
 // This method takes a non-cubic contour and resolves it into cubic beziers.
 // The implementation should also properly handle resolving the ContourOperation
@@ -162,7 +163,10 @@ impl<PD: PointData> ResolveCubic<PD> for MFEKContour<PD> {
                                     if pw.segs.len() < 1 {
                                         continue;
                                     }
-                                    let arc_len = ArcLengthParameterization::from(pw, 100);
+                                    let arc_len = AdaptiveArcLengthParameterization::from_arclength(
+                                        pw,
+                                        CLOSE_DISTANCE,
+                                    );
 
                                     for cut in pw.cuts.iter() {
                                         // TODO: Get VWS interpolation working by turning this bezpath into a piecewise.
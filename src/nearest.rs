@@ -0,0 +1,223 @@
+use crate::consts::{SMALL_DISTANCE, SMALL_T_DISTANCE};
+use crate::error::{MathError, MathResult};
+use crate::quadbezier::QuadBezier;
+use crate::roots::solve_polynomial;
+use crate::{Bezier, Evaluate, Piecewise, Vector};
+
+/// Projection of a point onto a curve. Results are (t, point on the curve, distance), for a
+/// piecewise t is the global t value.
+pub trait NearestPoint {
+    /// The closest point on the curve.
+    fn nearest_point(&self, point: Vector) -> (f64, Vector, f64);
+
+    /// Every place where the curve locally comes closest to the point and is within radius of it,
+    /// closest first. This includes the end points when the curve moves away from the point there.
+    fn nearest_points_within(&self, point: Vector, radius: f64) -> Vec<(f64, Vector, f64)>;
+
+    // Fallible version of nearest_point. A primitive always has a nearest point, Piecewise
+    // overrides this to report an empty contour.
+    fn try_nearest_point(&self, point: Vector) -> MathResult<(f64, Vector, f64)> {
+        Ok(self.nearest_point(point))
+    }
+}
+
+impl NearestPoint for Bezier {
+    fn nearest_point(&self, point: Vector) -> (f64, Vector, f64) {
+        closest(self, point, &self.projection_candidates(point))
+    }
+
+    fn nearest_points_within(&self, point: Vector, radius: f64) -> Vec<(f64, Vector, f64)> {
+        local_minima_within(self, point, radius, &self.projection_candidates(point))
+    }
+}
+
+impl NearestPoint for QuadBezier {
+    fn nearest_point(&self, point: Vector) -> (f64, Vector, f64) {
+        closest(self, point, &self.projection_candidates(point))
+    }
+
+    fn nearest_points_within(&self, point: Vector, radius: f64) -> Vec<(f64, Vector, f64)> {
+        local_minima_within(self, point, radius, &self.projection_candidates(point))
+    }
+}
+
+impl Bezier {
+    // The distance to the point is extremal where (B(t) - p) . B'(t) = 0, which for a cubic is a
+    // quintic. Its roots in 0-1 and the two end points are where the closest point can be.
+    fn projection_candidates(&self, point: Vector) -> Vec<f64> {
+        let [a, b, c, d] = self.power_coefficients();
+        let d = d - point;
+
+        let mut candidates = solve_polynomial(
+            &[
+                3. * a.dot(a),
+                5. * a.dot(b),
+                4. * a.dot(c) + 2. * b.dot(b),
+                3. * b.dot(c) + 3. * a.dot(d),
+                c.dot(c) + 2. * b.dot(d),
+                c.dot(d),
+            ],
+            0.,
+            1.,
+        );
+        candidates.extend_from_slice(&[0., 1.]);
+        candidates
+    }
+}
+
+impl QuadBezier {
+    // Same as for the cubic, but here (B(t) - p) . B'(t) is only a cubic.
+    fn projection_candidates(&self, point: Vector) -> Vec<f64> {
+        let [a, b, c] = self.power_coefficients();
+        let c = c - point;
+
+        let mut candidates = solve_polynomial(
+            &[
+                2. * a.dot(a),
+                3. * a.dot(b),
+                b.dot(b) + 2. * a.dot(c),
+                b.dot(c),
+            ],
+            0.,
+            1.,
+        );
+        candidates.extend_from_slice(&[0., 1.]);
+        candidates
+    }
+}
+
+fn project<T: Evaluate>(curve: &T, point: Vector, t: f64) -> (f64, Vector, f64) {
+    let on_curve = curve.at(t);
+    (t, on_curve, on_curve.distance(point))
+}
+
+fn closest<T: Evaluate>(curve: &T, point: Vector, candidates: &[f64]) -> (f64, Vector, f64) {
+    candidates
+        .iter()
+        .map(|t| project(curve, point, *t))
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .unwrap()
+}
+
+// Rather than looking at the sign of the second derivative we compare against our neighbours, this
+// works the same for the end points and for curves with colocated handles.
+fn is_local_minimum<T: Evaluate>(curve: &T, point: Vector, t: f64) -> bool {
+    let distance = (curve.at(t) - point).magnitude();
+
+    [t - SMALL_T_DISTANCE, t + SMALL_T_DISTANCE]
+        .iter()
+        .filter(|neighbour| (0. ..=1.).contains(*neighbour))
+        .all(|neighbour| (curve.at(*neighbour) - point).magnitude() >= distance)
+}
+
+fn local_minima_within<T: Evaluate>(
+    curve: &T,
+    point: Vector,
+    radius: f64,
+    candidates: &[f64],
+) -> Vec<(f64, Vector, f64)> {
+    let mut output: Vec<(f64, Vector, f64)> = candidates
+        .iter()
+        .map(|t| project(curve, point, *t))
+        .filter(|(t, _, distance)| *distance <= radius && is_local_minimum(curve, point, *t))
+        .collect();
+
+    output.sort_by(|a, b| a.2.total_cmp(&b.2));
+    output.dedup_by(|a, b| (a.0 - b.0).abs() <= SMALL_T_DISTANCE);
+    output
+}
+
+// This also covers whole outlines, where the segments are themselves piecewises.
+impl<T: Evaluate + NearestPoint> NearestPoint for Piecewise<T> {
+    fn nearest_point(&self, point: Vector) -> (f64, Vector, f64) {
+        self.try_nearest_point(point)
+            .unwrap_or_else(|err| panic!("Couldn't find the nearest point: {}", err))
+    }
+
+    fn try_nearest_point(&self, point: Vector) -> MathResult<(f64, Vector, f64)> {
+        let mut nearest: Option<(f64, Vector, f64)> = None;
+        for (i, seg) in self.segs.iter().enumerate() {
+            let (t, on_curve, distance) = seg
+                .try_nearest_point(point)
+                .map_err(MathError::in_segment(i))?;
+            let closer = match nearest {
+                Some((_, _, best)) => distance < best,
+                None => true,
+            };
+            if closer {
+                nearest = Some((self.global_t(i, t), on_curve, distance));
+            }
+        }
+
+        nearest.ok_or(MathError::EmptyPiecewise)
+    }
+
+    fn nearest_points_within(&self, point: Vector, radius: f64) -> Vec<(f64, Vector, f64)> {
+        let hits: Vec<Vec<(f64, Vector, f64)>> = self
+            .segs
+            .iter()
+            .map(|seg| seg.nearest_points_within(point, radius))
+            .collect();
+
+        let seg_count = self.segs.len();
+        let closed = seg_count > 0
+            && self.segs[seg_count - 1]
+                .end_point()
+                .is_near(self.segs[0].start_point(), SMALL_DISTANCE);
+
+        // The segments joined to the start and the end of segment i, if any.
+        let previous = |i: usize| {
+            match i {
+                0 if closed => Some(seg_count - 1),
+                0 => None,
+                _ => Some(i - 1),
+            }
+            .filter(|&p| {
+                self.segs[p]
+                    .end_point()
+                    .is_near(self.segs[i].start_point(), SMALL_DISTANCE)
+            })
+        };
+        let next = |i: usize| {
+            match i + 1 {
+                n if n == seg_count && closed => Some(0),
+                n if n == seg_count => None,
+                n => Some(n),
+            }
+            .filter(|&n| {
+                self.segs[i]
+                    .end_point()
+                    .is_near(self.segs[n].start_point(), SMALL_DISTANCE)
+            })
+        };
+        let has_hit_at = |i: usize, t: f64| {
+            hits[i]
+                .iter()
+                .any(|hit| (hit.0 - t).abs() <= SMALL_T_DISTANCE)
+        };
+
+        let mut output = vec![];
+        for (i, seg_hits) in hits.iter().enumerate() {
+            for &(t, on_curve, distance) in seg_hits {
+                // A segment's end point is only a minimum of the whole piecewise if the segment on
+                // the other side of the joint moves away from the point as well. When it does both
+                // segments report the joint, so we only keep the copy from the earlier segment.
+                if t <= SMALL_T_DISTANCE && previous(i).is_some() {
+                    continue;
+                }
+                if t >= 1. - SMALL_T_DISTANCE {
+                    if let Some(n) = next(i) {
+                        if !has_hit_at(n, 0.) {
+                            continue;
+                        }
+                    }
+                }
+
+                output.push((self.global_t(i, t), on_curve, distance));
+            }
+        }
+
+        output.sort_by(|a, b| a.2.total_cmp(&b.2));
+        output
+    }
+}
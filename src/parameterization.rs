@@ -1,3 +1,136 @@
+use crate::error::MathResult;
+
+// Bisection steps in the default inverse, enough to get down to the last bit of a t.
+const INVERSE_ITERATIONS: usize = 64;
+
+/// A mapping from u, how far along a curve we are by some measure as 0-1, to the curve's own t.
 pub trait Parameterization {
     fn parameterize(&self, u: f64) -> f64;
+
+    // Like parameterize but reports bad input instead of panicking or returning garbage.
+    fn try_parameterize(&self, u: f64) -> MathResult<f64> {
+        Ok(self.parameterize(u))
+    }
+
+    /// The inverse of parameterize, the u that maps to t. By default it's found by bisection,
+    /// which only needs parameterize to never go backwards.
+    fn inverse(&self, t: f64) -> f64 {
+        let (mut low, mut high) = (0., 1.);
+        for _ in 0..INVERSE_ITERATIONS {
+            let middle = (low + high) / 2.;
+            if self.parameterize(middle) < t {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        (low + high) / 2.
+    }
+
+    /// The whole curve by the measure u is a fraction of, its length for an arc length
+    /// parameterization or how far it turns for one by angle.
+    fn total(&self) -> f64 {
+        1.
+    }
+
+    /// Maps u through this parameterization and then the next one, for when this one gives the u
+    /// of the next rather than a t.
+    fn then<P: Parameterization>(self, next: P) -> Composed<Self, P>
+    where
+        Self: Sized,
+    {
+        Composed { first: self, next }
+    }
+
+    /// The same parameterization over just the part of the curve between t0 and t1, so u of 0
+    /// is at t0 and 1 is at t1.
+    fn restrict(self, t0: f64, t1: f64) -> Restricted<Self>
+    where
+        Self: Sized,
+    {
+        let u0 = self.inverse(t0);
+        let u1 = self.inverse(t1);
+        Restricted {
+            inner: self,
+            u0,
+            u1,
+        }
+    }
+}
+
+impl<P: Parameterization + ?Sized> Parameterization for &P {
+    fn parameterize(&self, u: f64) -> f64 {
+        (**self).parameterize(u)
+    }
+
+    fn try_parameterize(&self, u: f64) -> MathResult<f64> {
+        (**self).try_parameterize(u)
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        (**self).inverse(t)
+    }
+
+    fn total(&self) -> f64 {
+        (**self).total()
+    }
+}
+
+/// Two parameterizations one after the other, see [`Parameterization::then`]. Its total is the
+/// first one's, as that's the measure u is in.
+#[derive(Debug, Clone)]
+pub struct Composed<A, B> {
+    pub first: A,
+    pub next: B,
+}
+
+impl<A: Parameterization, B: Parameterization> Parameterization for Composed<A, B> {
+    fn parameterize(&self, u: f64) -> f64 {
+        self.next.parameterize(self.first.parameterize(u))
+    }
+
+    fn try_parameterize(&self, u: f64) -> MathResult<f64> {
+        self.next.try_parameterize(self.first.try_parameterize(u)?)
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        self.first.inverse(self.next.inverse(t))
+    }
+
+    fn total(&self) -> f64 {
+        self.first.total()
+    }
+}
+
+/// A parameterization of part of a curve, see [`Parameterization::restrict`]. The part runs from
+/// u0 to u1 of the whole curve's parameterization.
+#[derive(Debug, Clone)]
+pub struct Restricted<P> {
+    pub inner: P,
+    pub u0: f64,
+    pub u1: f64,
+}
+
+impl<P: Parameterization> Parameterization for Restricted<P> {
+    fn parameterize(&self, u: f64) -> f64 {
+        self.inner.parameterize(self.u0 + u * (self.u1 - self.u0))
+    }
+
+    fn try_parameterize(&self, u: f64) -> MathResult<f64> {
+        self.inner
+            .try_parameterize(self.u0 + u * (self.u1 - self.u0))
+    }
+
+    fn inverse(&self, t: f64) -> f64 {
+        if self.u1 == self.u0 {
+            return 0.;
+        }
+
+        (self.inner.inverse(t) - self.u0) / (self.u1 - self.u0)
+    }
+
+    fn total(&self) -> f64 {
+        self.inner.total() * (self.u1 - self.u0).abs()
+    }
 }
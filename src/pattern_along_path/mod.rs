@@ -2,13 +2,16 @@ use std::vec;
 
 use super::AngleParameterization;
 use super::{
-    ArcLengthParameterization, Bezier, EvalScale, EvalTranslate, Evaluate, Parameterization,
+    AdaptiveArcLengthParameterization, Affine, Bezier, EvalTransform, Evaluate, Parameterization,
     Piecewise, Rect, Vector,
 };
+use crate::area::Area;
+use crate::consts::CLOSE_DISTANCE;
+use crate::error::{MathError, MathResult};
+use crate::parameterization::Restricted;
 use crate::subdivide::Subdivide;
 use crate::{angleparameterization, vec2};
 
-use flo_curves::bezier::curve_intersects_ray;
 use glifparser::glif::contour_operations::pap::{
     PAPContour, PatternCopies, PatternCulling, PatternStretch, PatternSubdivide,
 };
@@ -50,18 +53,23 @@ fn prepare_pattern(
     let pattern_bounds = working_pattern.bounds();
     let pattern_height = f64::abs(pattern_bounds.bottom - pattern_bounds.top);
 
+    // we center the pattern and scale it in one go
+    let mut placement = Affine::IDENTITY;
     if settings.center_pattern {
         let pattern_offset_x = -pattern_bounds.left as f64 - 1.;
         let pattern_offset_y = -pattern_bounds.bottom as f64 - 1.;
 
-        working_pattern = working_pattern.translate(vec2!(
+        placement = Affine::translate(vec2!(
             pattern_offset_x,
             pattern_offset_y - pattern_height / 2.
         ));
     }
 
-    working_pattern =
-        working_pattern.scale(vec2!(settings.pattern_scale.x, settings.pattern_scale.y));
+    placement = placement.then(Affine::scale(vec2!(
+        settings.pattern_scale.x,
+        settings.pattern_scale.y
+    )));
+    working_pattern = working_pattern.transform(&placement);
 
     // if we've got a simple split we just do that now
     match settings.subdivide {
@@ -81,7 +89,7 @@ fn prepare_pattern(
 // point of each span.
 fn layout_spans(
     pattern: &Piecewise<Piecewise<Bezier>>,
-    arclenparam: &ArcLengthParameterization,
+    arclenparam: &AdaptiveArcLengthParameterization,
     settings: &PatternSettings,
     start_padding: f64,
     end_padding: f64,
@@ -168,7 +176,7 @@ fn pattern_along_path(
     // let's calculate the area of the pattern for later use
     let mut pattern_area = 0.;
     for contour in pattern.segs.iter() {
-        pattern_area += contour.approximate_area().abs();
+        pattern_area += contour.signed_area().abs();
     }
     let pattern_area = pattern_area;
 
@@ -177,7 +185,9 @@ fn pattern_along_path(
     }
     // we're gonna parameterize the input path such that 0-1 = 0 -> totalArcLength
     // this is important because samples will be spaced equidistant along the input path
-    let arclenparam = ArcLengthParameterization::from(path, 1000);
+    let arclenparam = AdaptiveArcLengthParameterization::from_arclength(path, CLOSE_DISTANCE);
+    // TODO: subdivide at CurvatureParameterization intervals as well, once glifparser's
+    // PatternSubdivide has a mode to ask for it.
     let angleparameterization = match settings.subdivide {
         PatternSubdivide::Angle(_) => Some(AngleParameterization::from(path, 1000)),
         _ => None,
@@ -213,26 +223,38 @@ fn pattern_along_path(
     let mut output_segments = Outline::new();
 
     for span in spans {
+        // The span's part of the path, where 0-1 runs across the span by arclength
+        let span_param = Restricted {
+            inner: &arclenparam,
+            u0: span.0 / total_arclen,
+            u1: span.1 / total_arclen,
+        };
+
+        // If the path needs to be reversed the span is mirrored to the other end of the path
+        let warp_param = if settings.reverse_path {
+            Restricted {
+                inner: &arclenparam,
+                u0: 1. - span_param.u0,
+                u1: 1. - span_param.u1,
+            }
+        } else {
+            span_param.clone()
+        };
+
         // This is the transform that we'll use to warp the pattern to the path.
         let warp_to_span = |point: &Vector| {
             // Calculate where along the path we are, if we're warping the path we'll use the x value of the point relative to the pattern width
             // if we're not warping the path we'll use the center of the span across the entire input pattern so that it is not distorted.
-            let u = span.0 + (span.1 - span.0) * (point.x / pattern_width);
-
-            // If the path needs to be reversed, modify u accordingly
-            let u = if settings.reverse_path { 1. - u } else { u };
-
-            // Parameterize u such that 0-1 maps to the curve by arclength
-            let t = arclenparam.parameterize(u / total_arclen);
+            let t = warp_param.parameterize(point.x / pattern_width);
             let path_point = path.at(t);
 
             // the derivative here is essentially a velocity or tangent line on the point we're evaulating
             // it faces in the direction of travel along the path
             let d = path.tangent_at(t);
 
-            // we rotate the vector by 90 degrees so that it's perpendicular to the direction of travel along the curve
-            // normalize the vector and now we've got a unit vector perpendicular to the curve's surface in 'curve space'
-            let N = Vector { x: d.y, y: -d.x }.normalize();
+            // the normal is perpendicular to the direction of travel along the curve, it's a unit vector
+            // pointing away from the curve's surface in 'curve space'
+            let N = path.normal_at(t);
 
             // now we multiply this by the y value of the pattern this gives us a point
             // that is as far away from the curve as the input is tall in the direction of the
@@ -252,37 +274,23 @@ fn pattern_along_path(
         };
 
         // When warp is off we just translate the pattern to the center of the span, and rotate it to match the path's tangent
-        let stamp_to_span = |point: &Vector| {
-            // Calculate the midpoint of the span
-            let u_mid = span.0 + (span.1 - span.0) * 0.5;
-
-            // Parameterize u_mid such that 0-1 maps to the curve by arclength
-            let t_mid = arclenparam.parameterize(u_mid / total_arclen);
+        let stamp_to_span = {
+            // Parameterize the midpoint of the span such that 0-1 maps to the curve by arclength
+            let t_mid = span_param.parameterize(0.5);
             let path_point_mid = path.at(t_mid);
 
-            // Derivative (tangent) at the midpoint
-            let d_mid = path.tangent_at(t_mid);
+            // Derivative (tangent) at the midpoint and the normal perpendicular to it
+            let d_mid = path.tangent_at(t_mid).normalize();
+            let N_mid = d_mid.right_normal();
 
-            // Generate the normal (perpendicular vector) based on the derivative
-            let N_mid = Vector {
-                x: d_mid.y,
-                y: -d_mid.x,
-            }
-            .normalize();
-
-            // Translate the point in the direction of the normal
-            let mut P = N_mid * point.y;
-            P = P + d_mid.normalize() * (point.x - pattern_width / 2.);
-
-            // Offset the point by the tangent offset setting
-            P = P + d_mid.normalize() * settings.tangent_offset;
-
-            // We offset the point by the normal offset setting
-            P = P + N_mid * settings.normal_offset;
+            // The pattern's x axis runs along the tangent with its center on the midpoint and its y
+            // axis along the normal, both shifted by the offset settings. Adding the midpoint of the
+            // bezier's point takes it to 'world space' where 0,0 is relative to the glyph origin
+            let offset = path_point_mid
+                + d_mid * (settings.tangent_offset - pattern_width / 2.)
+                + N_mid * settings.normal_offset;
 
-            // Add the midpoint of the bezier's point to the offset point
-            // this essentially translates P to 'world space' where 0,0 is relative to the glyph origin
-            P + path_point_mid
+            Affine::new([d_mid.x, d_mid.y, N_mid.x, N_mid.y, offset.x, offset.y])
         };
 
         let mut working_pattern = working_pattern.clone();
@@ -294,14 +302,8 @@ fn pattern_along_path(
                 PatternSubdivide::Angle(_) => {
                     let angle_intervals = angle_intervals.as_ref().unwrap();
 
-                    // first we need to convert the span's time parameters from 0->arclength to 0->1
-                    let span = (span.0 / total_arclen, span.1 / total_arclen);
-
-                    // now we need to take those times and find the unparameterized time parameter
-                    let span = (
-                        arclenparam.parameterize(span.0),
-                        arclenparam.parameterize(span.1),
-                    );
+                    // the unparameterized time parameters at either end of the span
+                    let span = (span_param.parameterize(0.), span_param.parameterize(1.));
 
                     // min and max t1, t2 to 0->1 range because floating point math is hard
                     let span = (span.0.min(1.).max(0.), span.1.min(1.).max(0.));
@@ -309,7 +311,7 @@ fn pattern_along_path(
                     let mut new_segments: Vec<Piecewise<Bezier>> = Vec::new();
 
                     // then for each interval we need to subdivide the beziers where they cross the interval
-                    // we do this by employing flo_curves to check for intersection against a vertical line
+                    // we do this by checking for intersection against a vertical line
                     for contour in working_pattern.segs.iter_mut() {
                         let mut new_contour: Vec<Bezier> = Vec::new();
                         for bez in contour.segs.iter_mut() {
@@ -318,7 +320,7 @@ fn pattern_along_path(
                                 if interval > &span.0 && interval < &span.1 {
                                     let x = (interval - span.0) / (span.1 - span.0) * pattern_width;
                                     let ray_intersections =
-                                        curve_intersects_ray(bez, &(vec2![x, 0.], vec2!(x, 1.)));
+                                        bez.ray_intersections(&(vec2![x, 0.], vec2!(x, 1.)));
 
                                     for intersection in ray_intersections {
                                         intersections.push(intersection.0);
@@ -345,7 +347,7 @@ fn pattern_along_path(
         let transformed_pattern = if settings.warp_pattern {
             working_pattern.apply_transform(warp_to_span)
         } else {
-            working_pattern.apply_transform(stamp_to_span)
+            working_pattern.transform(&stamp_to_span)
         };
 
         match settings.cull_overlap {
@@ -393,7 +395,7 @@ fn pattern_along_path(
                         let culled_pattern_pw = Piecewise::from(&culled_pattern);
 
                         for contour in culled_pattern_pw.segs {
-                            let area = contour.approximate_area().abs();
+                            let area = contour.signed_area().abs();
 
                             if area > cull_area_percent / 100. * pattern_area {
                                 output_segments.push(contour.to_contour::<MFEKPointData>());
@@ -480,6 +482,19 @@ pub fn pattern_along_path_mfek<PD: glifparser::PointData>(
     path: &Piecewise<Bezier>,
     settings: &PAPContour<PD>,
 ) -> Piecewise<Piecewise<Bezier>> {
+    try_pattern_along_path_mfek(path, settings)
+        .unwrap_or_else(|err| panic!("Couldn't lay the pattern along the path: {}", err))
+}
+
+// Fallible version of pattern_along_path_mfek, fails if the path is empty.
+pub fn try_pattern_along_path_mfek<PD: glifparser::PointData>(
+    path: &Piecewise<Bezier>,
+    settings: &PAPContour<PD>,
+) -> MathResult<Piecewise<Piecewise<Bezier>>> {
+    if path.segs.is_empty() {
+        return Err(MathError::EmptyPiecewise);
+    }
+
     // we're only doing this to avoid a circular dependency
     let split_settings = PatternSettings {
         copies: settings.copies.clone(),
@@ -504,12 +519,23 @@ pub fn pattern_along_path_mfek<PD: glifparser::PointData>(
     };
 
     let mut cull_dummy = skia_safe::Path::new();
-    return split_and_blit(
+    Ok(split_and_blit(
         path,
-        &(&settings.pattern).into(),
+        &without_empty_contours((&settings.pattern).into()),
         &split_settings,
         &mut cull_dummy,
-    );
+    ))
+}
+
+// Empty contours in a pattern don't draw anything, but they'd trip up measuring the pattern.
+fn without_empty_contours(pattern: Piecewise<Piecewise<Bezier>>) -> Piecewise<Piecewise<Bezier>> {
+    let segs = pattern
+        .segs
+        .into_iter()
+        .filter(|contour| !contour.segs.is_empty())
+        .collect();
+
+    Piecewise::new(segs, None)
 }
 
 pub fn pattern_along_glif<U: glifparser::PointData>(
@@ -518,12 +544,25 @@ pub fn pattern_along_glif<U: glifparser::PointData>(
     settings: &PatternSettings,
     marked_contour: Option<usize>,
 ) -> Glif<U> {
+    try_pattern_along_glif(path, pattern, settings, marked_contour)
+        .unwrap_or_else(|err| panic!("Couldn't lay the pattern along the glyph: {}", err))
+}
+
+// Fallible version of pattern_along_glif. Fails if the pattern has no outline or one of the
+// contours we lay it along is empty, in which case the error carries that contour's index.
+pub fn try_pattern_along_glif<U: glifparser::PointData>(
+    path: &Glif<U>,
+    pattern: &Glif<U>,
+    settings: &PatternSettings,
+    marked_contour: Option<usize>,
+) -> MathResult<Glif<U>> {
     // convert our path and pattern to piecewise collections of beziers
     let piece_path = match path.outline {
         Some(ref o) => Piecewise::from(o),
-        None => return path.clone(),
+        None => return Ok(path.clone()),
     };
-    let piece_pattern = Piecewise::from(pattern.outline.as_ref().unwrap());
+    let pattern_outline = pattern.outline.as_ref().ok_or(MathError::MissingOutline)?;
+    let piece_pattern = without_empty_contours(Piecewise::from(pattern_outline));
 
     let mut output_outline: Outline<U> = Vec::new();
     let mut cull_cache = skia_safe::Path::new();
@@ -537,6 +576,13 @@ pub fn pattern_along_glif<U: glifparser::PointData>(
             }
         }
 
+        if contour.segs.is_empty() {
+            return Err(MathError::InSegment {
+                index: idx,
+                error: Box::new(MathError::EmptyPiecewise),
+            });
+        }
+
         let result_outline = split_and_blit(contour, &piece_pattern, settings, &mut cull_cache);
 
         for result_contour in result_outline.segs {
@@ -544,7 +590,7 @@ pub fn pattern_along_glif<U: glifparser::PointData>(
         }
     }
 
-    return Glif {
+    Ok(Glif {
         outline: Some(output_outline),
         anchors: path.anchors.clone(),
         width: path.width,
@@ -557,5 +603,5 @@ pub fn pattern_along_glif<U: glifparser::PointData>(
         note: path.note.clone(),
         filename: path.filename.clone(),
         ..Glif::default()
-    };
+    })
 }
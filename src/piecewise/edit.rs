@@ -0,0 +1,273 @@
+use super::Piecewise;
+use crate::consts::SMALL_DISTANCE;
+use crate::error::{MathError, MathResult};
+use crate::evaluate::Evaluate;
+use crate::subdivide::Subdivide;
+
+// Edits keep the first and last cut where they are. The segments that stay keep their widths in t
+// relative to each other, new ones get the average width, and then they're all scaled to fit
+// between the first and last cut again. Evenly spaced cuts stay evenly spaced.
+impl<T: Evaluate> Piecewise<T> {
+    /// Like new, but refuses cuts that don't fit the segments, see [`Piecewise::check_cuts`].
+    pub fn try_new(segs: Vec<T>, cuts: Option<Vec<f64>>) -> MathResult<Self> {
+        let piecewise = Piecewise::new(segs, cuts);
+        piecewise.check_cuts()?;
+
+        Ok(piecewise)
+    }
+
+    /// Checks that there's a cut on either side of every segment, that they're all finite and
+    /// that they never go backwards.
+    pub fn check_cuts(&self) -> MathResult<()> {
+        if self.cuts.len() != self.segs.len() + 1 {
+            return Err(MathError::MismatchedCuts {
+                cuts: self.cuts.len(),
+                segs: self.segs.len(),
+            });
+        }
+        if let Some(&cut) = self.cuts.iter().find(|cut| !cut.is_finite()) {
+            return Err(MathError::InvalidParameter(cut));
+        }
+        if self.cuts.windows(2).any(|pair| pair[1] < pair[0]) {
+            return Err(MathError::UnorderedCuts);
+        }
+
+        Ok(())
+    }
+
+    /// Inserts seg before the segment at index, like [`Vec::insert`]. Where the segments on either
+    /// side of index met, seg has to meet both of them. If it doesn't the piecewise is left as it
+    /// was and we return [`MathError::Discontinuous`]. On a closed piecewise the last segment meets
+    /// the first, so that goes for seg at either end too.
+    ///
+    /// Panics if index is past the end.
+    pub fn insert_segment(&mut self, index: usize, seg: T) -> MathResult<()> {
+        self.check_cuts()?;
+        assert!(index <= self.segs.len(), "index past the end");
+
+        // at either end of a closed piecewise seg goes between the last segment and the first
+        let len = self.segs.len();
+        let closed = self.is_joined_up();
+        let before = match index {
+            0 if closed => self.segs.last(),
+            0 => None,
+            _ => Some(&self.segs[index - 1]),
+        };
+        let after = match self.segs.get(index) {
+            None if closed => self.segs.first(),
+            after => after,
+        };
+        let joined = match (before, after) {
+            (Some(before), Some(after)) => meets(before, after),
+            _ => true,
+        };
+        if joined {
+            if let Some(before) = before {
+                if !meets(before, &seg) {
+                    return Err(MathError::Discontinuous(index));
+                }
+            }
+            if let Some(after) = after {
+                if !meets(&seg, after) {
+                    return Err(MathError::Discontinuous((index + 1) % (len + 1)));
+                }
+            }
+        }
+
+        let mut widths = self.widths();
+        widths.insert(index, average_width(&widths));
+        self.segs.insert(index, seg);
+        self.set_widths(&widths);
+
+        Ok(())
+    }
+
+    /// Removes the segment at index and returns it. A segment from between two others that it
+    /// joined up can only go if they meet without it, which on a closed piecewise includes the
+    /// first and last segment.
+    ///
+    /// Panics if index is out of bounds.
+    pub fn remove_segment(&mut self, index: usize) -> MathResult<T> {
+        self.check_cuts()?;
+        assert!(index < self.segs.len(), "index out of bounds");
+
+        // the neighbours wrap around, but they only count where they meet
+        let len = self.segs.len();
+        let (before, seg, after) = (
+            &self.segs[(index + len - 1) % len],
+            &self.segs[index],
+            &self.segs[(index + 1) % len],
+        );
+        if meets(before, seg) && meets(seg, after) && !meets(before, after) {
+            return Err(MathError::Discontinuous(index % (len - 1)));
+        }
+
+        Ok(self.take_segment(index))
+    }
+
+    /// Puts seg in place of the segment at index and returns the one it replaced. It keeps the
+    /// old segment's share of t, and has to meet its neighbours wherever the old one did. On a
+    /// closed piecewise the first and last segment are neighbours.
+    ///
+    /// Panics if index is out of bounds.
+    pub fn replace_segment(&mut self, index: usize, seg: T) -> MathResult<T> {
+        self.check_cuts()?;
+        assert!(index < self.segs.len(), "index out of bounds");
+
+        // the neighbours wrap around, but they only count where they meet
+        let len = self.segs.len();
+        let (before, old, after) = (
+            &self.segs[(index + len - 1) % len],
+            &self.segs[index],
+            &self.segs[(index + 1) % len],
+        );
+        if meets(before, old) && !meets(before, &seg) {
+            return Err(MathError::Discontinuous(index));
+        }
+        if meets(old, after) && !meets(&seg, after) {
+            return Err(MathError::Discontinuous((index + 1) % len));
+        }
+
+        Ok(std::mem::replace(&mut self.segs[index], seg))
+    }
+
+    /// Adds seg to the end, where it has to start where the last segment ends and, on a closed
+    /// piecewise, end where the first one starts.
+    pub fn append(&mut self, seg: T) -> MathResult<()> {
+        self.insert_segment(self.segs.len(), seg)
+    }
+
+    /// Adds the segments of other to the end. Other's segments keep their widths relative to each
+    /// other, scaled so they're as wide on average as ours, and other has to start where we end.
+    /// If we're closed it also has to end where we start.
+    pub fn concat(&mut self, other: Piecewise<T>) -> MathResult<()> {
+        self.check_cuts()?;
+        other.check_cuts()?;
+
+        let closed = self.is_joined_up();
+        if let (Some(last), Some(next)) = (self.segs.last(), other.segs.first()) {
+            if !meets(last, next) {
+                return Err(MathError::Discontinuous(self.segs.len()));
+            }
+        }
+        if let (true, Some(first), Some(other_last)) =
+            (closed, self.segs.first(), other.segs.last())
+        {
+            if !meets(other_last, first) {
+                return Err(MathError::Discontinuous(0));
+            }
+        }
+        if self.segs.is_empty() {
+            *self = other;
+            return Ok(());
+        }
+
+        let mut widths = self.widths();
+        let other_widths = other.widths();
+        let other_average = average_width(&other_widths);
+        let scale = if other_average > 0. {
+            average_width(&widths) / other_average
+        } else {
+            1.
+        };
+        widths.extend(other_widths.iter().map(|width| width * scale));
+        self.segs.extend(other.segs);
+        self.set_widths(&widths);
+
+        Ok(())
+    }
+
+    // Whether the last segment meets the first, the same as is_closed but for any T.
+    fn is_joined_up(&self) -> bool {
+        match (self.segs.last(), self.segs.first()) {
+            (Some(last), Some(first)) => meets(last, first),
+            _ => false,
+        }
+    }
+
+    // Removes the segment at index without checking anything.
+    pub(super) fn take_segment(&mut self, index: usize) -> T {
+        let mut widths = self.widths();
+        widths.remove(index);
+        let seg = self.segs.remove(index);
+        self.set_widths(&widths);
+
+        seg
+    }
+
+    // How much of t each segment covers.
+    fn widths(&self) -> Vec<f64> {
+        self.cuts.windows(2).map(|pair| pair[1] - pair[0]).collect()
+    }
+
+    // Replaces the cuts with ones that give the segments these widths, scaled to fit between the
+    // first and last cut. With no last cut to go by the segments get 0-1.
+    fn set_widths(&mut self, widths: &[f64]) {
+        let first = self.cuts.first().copied().unwrap_or(0.);
+        let last = if self.cuts.len() > 1 {
+            self.cuts[self.cuts.len() - 1]
+        } else {
+            first + 1.
+        };
+
+        let total: f64 = widths.iter().sum();
+        let mut cuts = vec![first];
+        let mut sum = 0.;
+        for (i, width) in widths.iter().enumerate() {
+            sum += width;
+            let share = if total > 0. {
+                sum / total
+            } else {
+                (i + 1) as f64 / widths.len() as f64
+            };
+            cuts.push(first + (last - first) * share);
+        }
+        if !widths.is_empty() {
+            cuts[widths.len()] = last;
+        }
+
+        self.cuts = cuts;
+    }
+}
+
+impl<T: Evaluate + Subdivide> Piecewise<T> {
+    /// Splits the segment at index in two at its own t. The halves share its part of t the same
+    /// way they share the segment, so nothing moves. A t of 0 or 1 leaves nothing to split.
+    ///
+    /// Panics if index is out of bounds.
+    pub fn split_segment_at(&mut self, index: usize, t: f64) -> MathResult<()> {
+        self.check_cuts()?;
+        if !t.is_finite() || !(0. ..=1.).contains(&t) {
+            return Err(MathError::InvalidParameter(t));
+        }
+        assert!(index < self.segs.len(), "index out of bounds");
+
+        self.split_segment(index, t);
+
+        Ok(())
+    }
+
+    // Splits the segment at index at t without checking anything.
+    pub(super) fn split_segment(&mut self, index: usize, t: f64) {
+        if let Some((left, right)) = self.segs[index].split(t) {
+            let cut = self.global_t(index, t);
+            self.segs[index] = left;
+            self.segs.insert(index + 1, right);
+            self.cuts.insert(index + 1, cut);
+        }
+    }
+}
+
+// Whether b starts where a ends.
+fn meets<T: Evaluate>(a: &T, b: &T) -> bool {
+    a.end_point().is_near(b.start_point(), SMALL_DISTANCE)
+}
+
+// The width new segments get, 1 if there's nothing to take the average of.
+fn average_width(widths: &[f64]) -> f64 {
+    if widths.is_empty() {
+        return 1.;
+    }
+
+    widths.iter().sum::<f64>() / widths.len() as f64
+}
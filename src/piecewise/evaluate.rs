@@ -1,3 +1,4 @@
+use crate::error::{MathError, MathResult};
 use crate::evaluate::Evaluate;
 use crate::piecewise::Piecewise;
 use crate::rect::Rect;
@@ -47,24 +48,26 @@ impl<T: Evaluate + Send + Sync> Evaluate for Piecewise<T> {
         return dir.tangent_at(offset_time);
     }
 
-    fn bounds(&self) -> Rect {
-        // again maybe success/failure? These are mainly here to catch bugs right now.
-        if self.segs.len() == 0 {
-            panic!("An empty piecewise knows no bounds!")
-        }
+    fn second_derivative_at(&self, t: f64) -> Vector {
+        let curve_index = self.seg_n(t);
+        let offset_time = self.seg_t(t);
 
-        let mut output = Rect {
-            left: f64::INFINITY,
-            bottom: f64::INFINITY,
-            right: -f64::INFINITY,
-            top: -f64::INFINITY,
-        };
+        return self.segs[curve_index].second_derivative_at(offset_time);
+    }
 
-        for curve in &self.segs {
-            output = output.encapsulate_rect(curve.bounds());
-        }
+    // The degenerate handle fallback needs to happen on the segment so it looks at the right side
+    // of a joint, so we forward these rather than using the defaults.
+    fn normal_at(&self, t: f64) -> Vector {
+        return self.segs[self.seg_n(t)].normal_at(self.seg_t(t));
+    }
 
-        return output;
+    fn curvature_at(&self, t: f64) -> f64 {
+        return self.segs[self.seg_n(t)].curvature_at(self.seg_t(t));
+    }
+
+    fn bounds(&self) -> Rect {
+        self.try_bounds()
+            .unwrap_or_else(|err| panic!("Couldn't find the bounds: {}", err))
     }
 
     fn apply_transform<F: Send + Sync>(&self, transform: F) -> Self
@@ -81,20 +84,61 @@ impl<T: Evaluate + Send + Sync> Evaluate for Piecewise<T> {
     }
 
     fn start_point(&self) -> Vector {
-        if let Some(path_fcurve) = self.segs.first() {
-            return path_fcurve.start_point();
-        }
-
-        // TODO: Add proper error handling to these functions.
-        panic!("Empty piecewise has no start point.")
+        self.try_start_point()
+            .unwrap_or_else(|err| panic!("Couldn't find the start point: {}", err))
     }
 
     fn end_point(&self) -> Vector {
-        if let Some(path_lcurve) = self.segs.last() {
-            return path_lcurve.end_point();
+        self.try_end_point()
+            .unwrap_or_else(|err| panic!("Couldn't find the end point: {}", err))
+    }
+
+    fn try_at(&self, t: f64) -> MathResult<Vector> {
+        let curve_index = self.try_seg_n(t)?;
+
+        self.segs[curve_index]
+            .try_at(self.seg_t(t))
+            .map_err(MathError::in_segment(curve_index))
+    }
+
+    fn try_tangent_at(&self, t: f64) -> MathResult<Vector> {
+        let curve_index = self.try_seg_n(t)?;
+
+        self.segs[curve_index]
+            .try_tangent_at(self.seg_t(t))
+            .map_err(MathError::in_segment(curve_index))
+    }
+
+    fn try_bounds(&self) -> MathResult<Rect> {
+        if self.segs.is_empty() {
+            return Err(MathError::EmptyPiecewise);
+        }
+
+        let mut output = Rect {
+            left: f64::INFINITY,
+            bottom: f64::INFINITY,
+            right: -f64::INFINITY,
+            top: -f64::INFINITY,
+        };
+
+        for (i, curve) in self.segs.iter().enumerate() {
+            let bounds = curve.try_bounds().map_err(MathError::in_segment(i))?;
+            output = output.encapsulate_rect(bounds);
         }
 
-        // TODO: Add proper error handling to these functions.
-        panic!("Empty piecewise has no start point.")
+        Ok(output)
+    }
+
+    fn try_start_point(&self) -> MathResult<Vector> {
+        let first = self.segs.first().ok_or(MathError::EmptyPiecewise)?;
+
+        first.try_start_point().map_err(MathError::in_segment(0))
+    }
+
+    fn try_end_point(&self) -> MathResult<Vector> {
+        let last = self.segs.last().ok_or(MathError::EmptyPiecewise)?;
+
+        last.try_end_point()
+            .map_err(MathError::in_segment(self.segs.len() - 1))
     }
 }
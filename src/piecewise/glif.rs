@@ -1,4 +1,6 @@
+use crate::line::Line;
 use crate::quadbezier::QuadBezier;
+use crate::segment::Segment;
 
 use super::{Bezier, Piecewise, Vector};
 #[cfg(feature = "default")]
@@ -209,3 +211,96 @@ impl Piecewise<QuadBezier> {
         output_contour
     }
 }
+
+impl<T: glifparser::PointData> From<&Outline<T>> for Piecewise<Piecewise<Segment>> {
+    fn from(outline: &Outline<T>) -> Self {
+        let new_segs = outline.iter().map(Piecewise::from).collect();
+
+        return Piecewise::new(new_segs, None);
+    }
+}
+
+impl Piecewise<Piecewise<Segment>> {
+    pub fn to_outline<T: glifparser::PointData>(&self) -> Outline<T> {
+        return self
+            .segs
+            .iter()
+            .map(|contour| contour.to_contour())
+            .collect();
+    }
+}
+
+// A point's type describes the segment that ends at it, and in a closed contour the first point's
+// type describes the segment that closes it.
+impl<T: glifparser::PointData> From<&Contour<T>> for Piecewise<Segment> {
+    fn from(contour: &Contour<T>) -> Self {
+        let segment = |from: &glifparser::Point<T>, to: &glifparser::Point<T>| {
+            if to.ptype == PointType::Line {
+                Segment::Line(Line::from_points(
+                    Vector::from_point(from),
+                    Vector::from_point(to),
+                ))
+            } else {
+                Segment::Cubic(Bezier::from(from, to))
+            }
+        };
+
+        let mut new_segs: Vec<Segment> = contour
+            .windows(2)
+            .map(|pair| segment(&pair[0], &pair[1]))
+            .collect();
+
+        if let (Some(firstpoint), Some(lastpoint)) = (contour.first(), contour.last()) {
+            if firstpoint.ptype != PointType::Move {
+                new_segs.push(segment(lastpoint, firstpoint));
+            }
+        }
+
+        return Piecewise::new(new_segs, None);
+    }
+}
+
+impl Piecewise<Segment> {
+    pub fn to_contour<T: glifparser::PointData>(&self) -> Contour<T> {
+        let mut output_contour: Contour<T> = Vec::new();
+        if self.segs.is_empty() {
+            return output_contour;
+        }
+
+        // lines keep their handles colocated, everything else is written out as a cubic
+        let handles = |seg: &Segment| match seg {
+            Segment::Line(_) => (Handle::Colocated, Handle::Colocated),
+            _ => {
+                let cubic = seg.to_cubic();
+                (cubic.w2.to_handle(), cubic.w3.to_handle())
+            }
+        };
+        let point_type = |seg: &Segment| match seg {
+            Segment::Line(_) => PointType::Line,
+            _ => PointType::Curve,
+        };
+
+        let closed = self.is_closed();
+        let last_seg = self.segs.last().unwrap();
+
+        for (i, seg) in self.segs.iter().enumerate() {
+            let (ptype, handle_b) = match i {
+                0 if closed => (point_type(last_seg), handles(last_seg).1),
+                0 => (PointType::Move, Handle::Colocated),
+                _ => (point_type(&self.segs[i - 1]), handles(&self.segs[i - 1]).1),
+            };
+
+            output_contour.push(seg.start_point().to_point(handles(seg).0, handle_b, ptype));
+        }
+
+        if !closed {
+            output_contour.push(last_seg.end_point().to_point(
+                Handle::Colocated,
+                handles(last_seg).1,
+                point_type(last_seg),
+            ));
+        }
+
+        output_contour
+    }
+}
@@ -1,13 +1,18 @@
+mod edit;
 mod evaluate;
 pub mod glif;
+mod simplify;
 #[cfg(feature = "skia")]
 mod skia;
 
 use crate::consts::SMALL_DISTANCE;
+use crate::error::{MathError, MathResult};
 
+use crate::arclength::ArcLength;
 use crate::arclenparameterization::ArcLengthParameterization;
 use crate::bezier::Bezier;
-use crate::evaluate::Evaluate;
+use crate::evaluate::{nondegenerate_t, Evaluate};
+use crate::parameterization::Parameterization;
 use crate::subdivide::Subdivide;
 use crate::vector::Vector;
 
@@ -77,9 +82,79 @@ impl<T: Evaluate> Piecewise<T> {
         panic!("Couldn't find the target segment!");
     }
 
+    // Fallible version of seg_n that reports an empty piecewise or a t that's outside of the cuts,
+    // NaN included.
+    pub fn try_seg_n(&self, t: f64) -> MathResult<usize> {
+        if self.segs.is_empty() || self.cuts.len() < 2 {
+            return Err(MathError::EmptyPiecewise);
+        }
+        if !(self.cuts[0]..=self.cuts[self.cuts.len() - 1]).contains(&t) {
+            return Err(MathError::InvalidParameter(t));
+        }
+
+        Ok(self.seg_n(t))
+    }
+
+    pub fn try_seg_t(&self, t: f64) -> MathResult<f64> {
+        self.try_seg_n(t)?;
+
+        Ok(self.seg_t(t))
+    }
+
     pub fn seg_t(&self, t: f64) -> f64 {
         let i = self.seg_n(t);
-        return (t - self.cuts[i]) / (self.cuts[i + 1] - self.cuts[i]);
+        let width = self.cuts[i + 1] - self.cuts[i];
+        // a segment with no length gets no t when the cuts follow arc length
+        if width == 0. {
+            return 0.;
+        }
+        return (t - self.cuts[i]) / width;
+    }
+
+    // The inverse of seg_t, maps a time on segment n back to the time on the whole piecewise.
+    pub fn global_t(&self, n: usize, t: f64) -> f64 {
+        return self.cuts[n] + t * (self.cuts[n + 1] - self.cuts[n]);
+    }
+}
+
+impl<T: Evaluate + Clone> Piecewise<T> {
+    /// The same segments with cuts that put each join where the given parameterization of the
+    /// piecewise puts it, keeping the first and last cut where they are. With an arc length
+    /// parameterization each segment gets a share of t as big as its share of the length.
+    pub fn with_cuts_from<P: Parameterization>(&self, parameterization: &P) -> Self {
+        let units = self.cuts.iter().map(|&cut| parameterization.inverse(cut));
+        let cuts = self.rescaled_cuts(units);
+        Piecewise::new(self.segs.clone(), Some(cuts))
+    }
+
+    // Takes new cuts as 0-1 into the range the current cuts cover.
+    fn rescaled_cuts<I: Iterator<Item = f64>>(&self, units: I) -> Vec<f64> {
+        let (first, last) = match (self.cuts.first(), self.cuts.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return Vec::new(),
+        };
+
+        units.map(|unit| first + (last - first) * unit).collect()
+    }
+}
+
+impl<T: Evaluate + ArcLength + Clone> Piecewise<T> {
+    /// The same segments with cuts spaced by their lengths, so a short serif gets less of t than a
+    /// long bowl. Within each segment t still follows the segment's own t.
+    pub fn with_arclength_cuts(&self, tolerance: f64) -> Self {
+        let tolerance = tolerance / self.segs.len().max(1) as f64;
+        let mut lengths = vec![0.];
+        for seg in &self.segs {
+            lengths.push(lengths[lengths.len() - 1] + seg.arclen(tolerance));
+        }
+
+        let total = lengths[lengths.len() - 1];
+        if total == 0. {
+            return self.clone();
+        }
+
+        let cuts = self.rescaled_cuts(lengths.iter().map(|length| length / total));
+        Piecewise::new(self.segs.clone(), Some(cuts))
     }
 }
 
@@ -99,6 +174,17 @@ impl<T: Evaluate + Subdivide + Send + Sync + Clone> Piecewise<Piecewise<T>> {
 impl Piecewise<Bezier> {
     pub fn balance(&self) -> Self {
         let new_segments = self.segs.iter().map(|bezier| bezier.balance()).collect();
+        Piecewise::new(new_segments, Some(self.cuts.clone()))
+    }
+
+    /// Offsets every segment, see [`Bezier::offset`]. Where the path has a corner the offsets of
+    /// the segments on either side of it don't meet, [`crate::expand_stroke`] joins them up.
+    pub fn offset(&self, distance: f64, tolerance: f64) -> Piecewise<Bezier> {
+        let new_segments = self
+            .segs
+            .iter()
+            .flat_map(|bezier| bezier.offset(distance, tolerance))
+            .collect();
         Piecewise::new(new_segments, None)
     }
 }
@@ -110,17 +196,18 @@ impl Piecewise<Piecewise<Bezier>> {
             .iter()
             .map(|piecewise| piecewise.balance())
             .collect();
-        Piecewise::new(new_segments, None)
+        Piecewise::new(new_segments, Some(self.cuts.clone()))
     }
 }
 
 impl Piecewise<Bezier> {
     pub fn fuse_nearby_ends(&self, distance: f64) -> Piecewise<Bezier> {
-        let mut iter = self.segs.iter().peekable();
         let mut new_segments = Vec::new();
-        while let Some(primitive) = iter.next() {
-            while let Some(next_primitive) = iter.peek() {
-                if primitive.end_point().distance(next_primitive.start_point()) <= distance {
+        for (i, primitive) in self.segs.iter().enumerate() {
+            match self.segs.get(i + 1) {
+                Some(next_primitive)
+                    if primitive.end_point().distance(next_primitive.start_point()) <= distance =>
+                {
                     let mut new_primitive = primitive.to_control_points();
                     new_primitive[3] = next_primitive.start_point();
                     new_segments.push(Bezier::from_points(
@@ -129,9 +216,8 @@ impl Piecewise<Bezier> {
                         new_primitive[2],
                         new_primitive[3],
                     ));
-                } else {
-                    new_segments.push(primitive.clone());
                 }
+                _ => new_segments.push(primitive.clone()),
             }
         }
 
@@ -151,21 +237,44 @@ impl Piecewise<Bezier> {
         return Piecewise::new(new_segs, None);
     }
 
+    /// Removes the segments that are no longer than len, and their cuts with them. The gap each
+    /// one leaves is closed by moving the start of the segment after it back to where it started,
+    /// or if it was the last segment the end of the one before it forward to where it ended.
+    pub fn remove_short_segs_within(&self, len: f64, tolerance: f64) -> Piecewise<Bezier> {
+        let mut output = self.clone();
+        let mut i = 0;
+        while i < output.segs.len() {
+            let short = output.segs[i].clone();
+            if short.arclen(tolerance) > len {
+                i += 1;
+                continue;
+            }
+
+            if let Some(next) = output.segs.get(i + 1) {
+                let offset = short.w1 - next.w1;
+                output.segs[i + 1] =
+                    Bezier::from_points(short.w1, next.w2 + offset, next.w3, next.w4);
+            } else if i > 0 {
+                let previous = &output.segs[i - 1];
+                let offset = short.w4 - previous.w4;
+                output.segs[i - 1] =
+                    Bezier::from_points(previous.w1, previous.w2, previous.w3 + offset, short.w4);
+            }
+            output.take_segment(i);
+        }
+
+        output
+    }
+
     pub fn split_at_tangent_discontinuities(&self, angle: f64) -> Piecewise<Piecewise<Bezier>> {
         let mut output_pws: Vec<Piecewise<Bezier>> = Vec::new();
         let mut current_run: Vec<Bezier> = Vec::new();
-        let mut last_tangent: Option<Vector> = None;
+        let mut last: Option<&Bezier> = None;
 
         for bez in &self.segs {
-            let start_tangent = bez.tangent_at(0.0);
-
-            // Compare this tangent to the last one
-            if let Some(lt) = last_tangent {
-                let dot_product = lt.dot(start_tangent);
-                let cos_angle = dot_product / (lt.magnitude() * start_tangent.magnitude()); // Make sure to normalize
-                let current_angle = cos_angle.acos(); // in radians
-
-                if current_angle > angle {
+            // Compare this segment's start to the end of the last one
+            if let Some(last) = last {
+                if is_corner(last, bez, angle) {
                     // A discontinuity is detected
                     let output = Piecewise::new(current_run.clone(), None);
                     output_pws.push(output);
@@ -179,7 +288,7 @@ impl Piecewise<Bezier> {
                 current_run.push(bez.clone());
             }
 
-            last_tangent = Some(bez.tangent_at(1.0));
+            last = Some(bez);
         }
 
         // Handle any remaining Bezier curves
@@ -221,7 +330,8 @@ impl Piecewise<Bezier> {
         return Piecewise::new(output_pws, None);
     }
 
-    /// Calculate the approximate area by treating each Bezier curve as a line segment
+    /// Calculate the approximate area by treating each Bezier curve as a line segment. Use
+    /// [`crate::Area::signed_area`] for the exact area.
     pub fn approximate_area(&self) -> f64 {
         let mut area = 0.0;
         let mut prev_point: Option<Vector> = None;
@@ -263,6 +373,21 @@ impl Piecewise<Bezier> {
     }
 }
 
+// Whether b leaves at a sharper angle, in radians, than angle from the direction a arrives in.
+// Where a handle sits on its point we measure from just inside the segment, like normal_at does,
+// so lines with colocated handles still make corners with the curves they meet.
+pub(crate) fn is_corner(a: &Bezier, b: &Bezier, angle: f64) -> bool {
+    let incoming = a.tangent_at(nondegenerate_t(a, 1.));
+    let outgoing = b.tangent_at(nondegenerate_t(b, 0.));
+
+    let cos_angle = incoming.dot(outgoing) / (incoming.magnitude() * outgoing.magnitude());
+    if cos_angle.is_nan() {
+        return false;
+    }
+
+    cos_angle.clamp(-1., 1.).acos() > angle
+}
+
 impl<T: Evaluate + Subdivide + Send + Sync + Clone> Piecewise<T> {
     pub fn is_closed(&self) -> bool {
         if self.start_point().is_near(self.end_point(), SMALL_DISTANCE) {
@@ -271,38 +396,15 @@ impl<T: Evaluate + Subdivide + Send + Sync + Clone> Piecewise<T> {
         return false;
     }
 
+    // Splits every segment at its own t, with a cut where each one was split.
     pub fn subdivide(&self, t: f64) -> Piecewise<T> {
-        let mut new_segments = Vec::new();
-        let mut new_cuts = Vec::new();
-        for primitive in &self.segs {
-            let subdivisions = primitive.split(t);
-
-            match subdivisions {
-                Some(subs) => {
-                    new_segments.push(subs.0);
-                    new_segments.push(subs.1);
-                }
-                _ => {
-                    new_segments.push(primitive.clone());
-                }
-            }
+        let mut output = self.clone();
+        // backwards, so the segments that are still to be split stay where they were
+        for i in (0..self.segs.len()).rev() {
+            output.split_segment(i, t);
         }
 
-        let mut last_cut = None;
-        for cut in &self.cuts {
-            if let Some(lcut) = last_cut {
-                if t > lcut && t < *cut {
-                    new_cuts.push(t);
-                    last_cut = Some(t);
-                } else {
-                    last_cut = Some(*cut);
-                }
-            }
-
-            new_cuts.push(*cut);
-        }
-
-        return Piecewise::new(new_segments, Some(new_cuts));
+        output
     }
 
     pub fn cut_at_t(&self, t: f64) -> Piecewise<T> {
@@ -349,6 +451,90 @@ impl<T: Evaluate + Subdivide + Send + Sync + Clone> Piecewise<T> {
 
         return Piecewise::new(new_segments, Some(new_cuts));
     }
+
+    /// Just the part of the piecewise between t0 and t1, with fresh cuts from 0 to 1 that keep
+    /// each piece's share of t. On a closed piecewise a t0 after t1 wraps around through the
+    /// start, on an open one the two are swapped.
+    pub fn portion(&self, t0: f64, t1: f64) -> Piecewise<T> {
+        let first = self.cuts[0];
+        let last = self.cuts[self.cuts.len() - 1];
+        let t0 = t0.clamp(first, last);
+        let t1 = t1.clamp(first, last);
+
+        let mut pieces = Vec::new();
+        if t0 <= t1 {
+            self.portion_pieces(t0, t1, &mut pieces);
+        } else if self.is_closed() {
+            // an end right on the seam leaves nothing on its side of it, unless that's all there is
+            if t0 < last || t1 == first {
+                self.portion_pieces(t0, last, &mut pieces);
+            }
+            if t1 > first {
+                self.portion_pieces(first, t1, &mut pieces);
+            }
+        } else {
+            self.portion_pieces(t1, t0, &mut pieces);
+        }
+
+        let total: f64 = pieces.iter().map(|(_, width)| width).sum();
+        let (segs, widths): (Vec<T>, Vec<f64>) = pieces.into_iter().unzip();
+        if total == 0. {
+            return Piecewise::new(segs, None);
+        }
+
+        let mut cuts = vec![0.];
+        let mut sum = 0.;
+        for width in &widths {
+            sum += width;
+            cuts.push(sum / total);
+        }
+        cuts[widths.len()] = 1.;
+
+        Piecewise::new(segs, Some(cuts))
+    }
+
+    // Fallible version of portion that reports an empty piecewise or a t that's outside of the cuts.
+    pub fn try_portion(&self, t0: f64, t1: f64) -> MathResult<Piecewise<T>> {
+        self.try_seg_n(t0)?;
+        self.try_seg_n(t1)?;
+
+        Ok(self.portion(t0, t1))
+    }
+
+    // Pushes the pieces of the segments between t0 and t1 along with how much of t each covers. A
+    // t right on a join ends the segment before it or starts the one after, so neither end gets a
+    // piece with nothing in it.
+    fn portion_pieces(&self, t0: f64, t1: f64, pieces: &mut Vec<(T, f64)>) {
+        let local_t = |n: usize, t: f64| {
+            let width = self.cuts[n + 1] - self.cuts[n];
+            if width == 0. {
+                0.
+            } else {
+                ((t - self.cuts[n]) / width).clamp(0., 1.)
+            }
+        };
+
+        let mut start_n = self.seg_n(t0);
+        let mut end_n = self.seg_n(t1);
+        if start_n < end_n && t0 >= self.cuts[start_n + 1] {
+            start_n += 1;
+        }
+        if end_n > start_n && t1 <= self.cuts[end_n] {
+            end_n -= 1;
+        }
+
+        for n in start_n..=end_n {
+            let start = if n == start_n { local_t(n, t0) } else { 0. };
+            let end = if n == end_n { local_t(n, t1) } else { 1. };
+            let seg = if start == 0. && end == 1. {
+                self.segs[n].clone()
+            } else {
+                self.segs[n].subsegment(start, end)
+            };
+
+            pieces.push((seg, (end - start) * (self.cuts[n + 1] - self.cuts[n])));
+        }
+    }
 }
 
 // Returns a primitive and the range of t values that it covers.
@@ -0,0 +1,151 @@
+use super::{is_corner, Piecewise};
+use crate::fit_to_points::fit_cubic_with_tangents;
+use crate::fit_to_points::least_squares::reparameterize;
+use crate::nearest::NearestPoint;
+use crate::{Bezier, Evaluate, Vector};
+
+// Points taken from each segment to fit the merged cubic to.
+const SAMPLES_PER_SEGMENT: usize = 8;
+
+// Rounds of Newton's method on the t values of the samples between fits.
+const REPARAMETERIZE_ITERATIONS: usize = 4;
+
+impl Piecewise<Bezier> {
+    /// Merges runs of segments into single cubics wherever the cubic stays within tolerance of
+    /// the segments it replaces. Segments never get merged across the start of the path nor
+    /// across a corner, where they meet at a sharper angle in radians than corner_angle, the way
+    /// split_at_tangent_discontinuities finds them. Returns the simplified path and the number of
+    /// nodes that were removed.
+    pub fn simplify(&self, tolerance: f64, corner_angle: f64) -> (Piecewise<Bezier>, usize) {
+        let mut new_segments = Vec::new();
+
+        for segs in smooth_runs(&self.segs, corner_angle) {
+            let mut start = 0;
+
+            while start < segs.len() {
+                let mut merged = segs[start].clone();
+                let mut end = start + 1;
+
+                while end < segs.len() {
+                    match merge_segments(&segs[start..=end], tolerance) {
+                        Some(bez) => {
+                            merged = bez;
+                            end += 1;
+                        }
+                        None => break,
+                    }
+                }
+
+                new_segments.push(merged);
+                start = end;
+            }
+        }
+
+        let removed = self.segs.len() - new_segments.len();
+        (Piecewise::new(new_segments, None), removed)
+    }
+}
+
+impl Piecewise<Piecewise<Bezier>> {
+    pub fn simplify(
+        &self,
+        tolerance: f64,
+        corner_angle: f64,
+    ) -> (Piecewise<Piecewise<Bezier>>, usize) {
+        let mut removed = 0;
+        let new_segments = self
+            .segs
+            .iter()
+            .map(|contour| {
+                let (simplified, contour_removed) = contour.simplify(tolerance, corner_angle);
+                removed += contour_removed;
+                simplified
+            })
+            .collect();
+
+        (
+            Piecewise::new(new_segments, Some(self.cuts.clone())),
+            removed,
+        )
+    }
+}
+
+// Splits the segments up at the corners between them.
+fn smooth_runs(segs: &[Bezier], corner_angle: f64) -> Vec<&[Bezier]> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..segs.len() {
+        if is_corner(&segs[i - 1], &segs[i], corner_angle) {
+            runs.push(&segs[start..i]);
+            start = i;
+        }
+    }
+    if start < segs.len() {
+        runs.push(&segs[start..]);
+    }
+
+    runs
+}
+
+// Fits a single cubic to the segments, None if it can't be done within the tolerance.
+fn merge_segments(segs: &[Bezier], tolerance: f64) -> Option<Bezier> {
+    let (points, mut ts) = samples(segs);
+    let start_tangent = segs[0].tangent_at(0.);
+    let end_tangent = segs[segs.len() - 1].tangent_at(1.);
+
+    let mut fitted = fit_cubic_with_tangents(&points, &ts, start_tangent, end_tangent);
+    for _ in 0..REPARAMETERIZE_ITERATIONS {
+        ts = reparameterize(&fitted, &points, &ts);
+        fitted = fit_cubic_with_tangents(&points, &ts, start_tangent, end_tangent);
+    }
+
+    // the cubic has to stay close to the segments and the segments to the cubic
+    let samples = segs.len() * SAMPLES_PER_SEGMENT;
+    let fits = points
+        .iter()
+        .all(|point| fitted.nearest_point(*point).2 <= tolerance)
+        && (0..=samples).all(|i| {
+            let point = fitted.at(i as f64 / samples as f64);
+            segs.iter()
+                .any(|bez| bez.nearest_point(point).2 <= tolerance)
+        });
+
+    if fits {
+        Some(fitted)
+    } else {
+        None
+    }
+}
+
+// Points along the segments and the t values they'd have on a single cubic. If the segments were
+// split off of a cubic the speed of the parameter is the same on either side of a split, so the
+// share of t each segment gets follows from the lengths of the handles meeting there. That gets
+// the t values right for segments split off of a cubic, and close otherwise.
+fn samples(segs: &[Bezier]) -> (Vec<Vector>, Vec<f64>) {
+    let mut spans = vec![1.];
+    for pair in segs.windows(2) {
+        let incoming = pair[0].w4.distance(pair[0].w3);
+        let outgoing = pair[1].w2.distance(pair[1].w1);
+        let ratio = if incoming > 0. && outgoing > 0. {
+            outgoing / incoming
+        } else {
+            pair[1].w1.distance(pair[1].w4) / pair[0].w1.distance(pair[0].w4)
+        };
+        spans.push(spans[spans.len() - 1] * ratio);
+    }
+    let total: f64 = spans.iter().sum();
+
+    let mut points = vec![segs[0].start_point()];
+    let mut ts = vec![0.];
+    let mut start = 0.;
+    for (bez, span) in segs.iter().zip(&spans) {
+        for i in 1..=SAMPLES_PER_SEGMENT {
+            let t = i as f64 / SAMPLES_PER_SEGMENT as f64;
+            points.push(bez.at(t));
+            ts.push((start + span * t) / total);
+        }
+        start += span;
+    }
+
+    (points, ts)
+}
@@ -1,8 +1,17 @@
 use super::super::bezier::Bezier;
 use super::super::piecewise::Piecewise;
 use super::super::vector::Vector;
+use crate::conic::Conic;
+use crate::consts::SMALL_DISTANCE;
+use crate::line::Line;
+use crate::quadbezier::QuadBezier;
+use crate::segment::Segment;
+use crate::Evaluate;
 use skia_safe::{path, Path, Point};
 
+// How far the cubics we replace skia's conics with may stray from them.
+const CONIC_TOLERANCE: f64 = SMALL_DISTANCE;
+
 impl Piecewise<Piecewise<Bezier>> {
     pub fn to_skpath(self) -> Path {
         let path = Path::new();
@@ -29,9 +38,10 @@ impl Piecewise<Bezier> {
                 first = false;
             }
 
-            // we've got ourselves a line
-            if controlp[0] == controlp[2] && controlp[1] == controlp[3] {
+            // we've got ourselves a line, these are stored as cubics with colocated handles
+            if controlp[0] == controlp[1] && controlp[2] == controlp[3] {
                 skpath.line_to(controlp[3].to_skia_point());
+                continue;
             }
 
             skpath.cubic_to(
@@ -48,11 +58,11 @@ impl Piecewise<Bezier> {
 impl From<&Path> for Piecewise<Piecewise<Bezier>> {
     fn from(ipath: &Path) -> Self {
         let mut contours: Vec<Piecewise<Bezier>> = Vec::new();
-        let iter = path::Iter::new(ipath, false);
+        let mut iter = path::Iter::new(ipath, false);
 
         let mut cur_contour: Vec<Bezier> = Vec::new();
-        let mut last_point: Vector = Vector { x: 0., y: 0. }; // don't think we need this?
-        for (v, vp) in iter {
+        // we need the iterator itself to ask it for conic weights, so no for loop here
+        while let Some((v, vp)) = iter.next() {
             match v {
                 path::Verb::Move => {
                     if !cur_contour.is_empty() {
@@ -60,22 +70,28 @@ impl From<&Path> for Piecewise<Piecewise<Bezier>> {
                     }
 
                     cur_contour = Vec::new();
-                    last_point = Vector::from_skia_point(vp.first().unwrap());
                 }
 
                 path::Verb::Line => {
                     let lp = Vector::from_skia_point(&vp[0]);
                     let np = Vector::from_skia_point(&vp[1]);
                     cur_contour.push(Bezier::from_points(lp, lp, np, np));
-                    last_point = np;
                 }
 
                 path::Verb::Quad => {
-                    let lp = last_point;
-                    let h2 = Vector::from_skia_point(&vp[0]);
-                    let np = Vector::from_skia_point(&vp[1]);
-                    cur_contour.push(Bezier::from_points(lp, lp, h2, np));
-                    last_point = np;
+                    let lp = Vector::from_skia_point(&vp[0]);
+                    let h = Vector::from_skia_point(&vp[1]);
+                    let np = Vector::from_skia_point(&vp[2]);
+                    cur_contour.push(QuadBezier::from_points(lp, h, np).to_cubic());
+                }
+
+                path::Verb::Conic => {
+                    let lp = Vector::from_skia_point(&vp[0]);
+                    let h = Vector::from_skia_point(&vp[1]);
+                    let np = Vector::from_skia_point(&vp[2]);
+                    let weight = iter.conic_weight().unwrap_or(1.) as f64;
+                    cur_contour
+                        .extend(Conic::from_points(lp, h, np, weight).to_cubics(CONIC_TOLERANCE));
                 }
 
                 path::Verb::Cubic => {
@@ -84,20 +100,138 @@ impl From<&Path> for Piecewise<Piecewise<Bezier>> {
                     let h2 = Vector::from_skia_point(&vp[2]);
                     let np = Vector::from_skia_point(&vp[3]);
                     cur_contour.push(Bezier::from_points(lp, h1, h2, np));
-                    last_point = np;
                 }
 
+                // closing a contour draws a line back to where it started if it isn't there yet
                 path::Verb::Close => {
-                    contours.push(Piecewise::new(cur_contour.clone(), None));
+                    if let (Some(first), Some(last)) = (cur_contour.first(), cur_contour.last()) {
+                        let (start, end) = (first.w1, last.w4);
+                        if start != end {
+                            cur_contour.push(Bezier::from_points(end, end, start, start));
+                        }
+
+                        contours.push(Piecewise::new(cur_contour, None));
+                    }
+
+                    cur_contour = Vec::new();
+                }
+
+                path::Verb::Done => break,
+            }
+        }
+
+        if !cur_contour.is_empty() {
+            contours.push(Piecewise::new(cur_contour, None));
+        }
+
+        return Piecewise::new(contours, None);
+    }
+}
+
+impl Piecewise<Piecewise<Segment>> {
+    pub fn to_skpath(self) -> Path {
+        let path = Path::new();
+        return self.append_to_skpath(path);
+    }
+
+    pub fn append_to_skpath(&self, mut skpath: Path) -> Path {
+        for contour in &self.segs {
+            skpath = contour.append_to_skpath(skpath);
+        }
+
+        return skpath;
+    }
+}
+
+impl Piecewise<Segment> {
+    pub fn append_to_skpath(&self, mut skpath: Path) -> Path {
+        if let Some(first) = self.segs.first() {
+            skpath.move_to(first.start_point().to_skia_point());
+        }
+
+        for seg in &self.segs {
+            match seg {
+                Segment::Line(line) => {
+                    skpath.line_to(line.w2.to_skia_point());
+                }
+                Segment::Quad(quad) => {
+                    skpath.quad_to(quad.w2.to_skia_point(), quad.w3.to_skia_point());
+                }
+                Segment::Cubic(bez) => {
+                    skpath.cubic_to(
+                        bez.w2.to_skia_point(),
+                        bez.w3.to_skia_point(),
+                        bez.w4.to_skia_point(),
+                    );
+                }
+            }
+        }
+
+        return skpath;
+    }
+}
+
+impl From<&Path> for Piecewise<Piecewise<Segment>> {
+    fn from(ipath: &Path) -> Self {
+        let mut contours: Vec<Piecewise<Segment>> = Vec::new();
+        let mut iter = path::Iter::new(ipath, false);
+
+        let mut cur_contour: Vec<Segment> = Vec::new();
+        while let Some((v, vp)) = iter.next() {
+            let points: Vec<Vector> = vp.iter().map(Vector::from_skia_point).collect();
+
+            match v {
+                path::Verb::Move => {
+                    if !cur_contour.is_empty() {
+                        contours.push(Piecewise::new(cur_contour, None));
+                    }
+
                     cur_contour = Vec::new();
                 }
 
-                // I might have to implement more verbs, but at the moment we're just converting
-                // from glifparser output and these are all the supported primitives there.
-                _ => {
-                    println!("{:?} {:?}", v, vp);
-                    panic!("Unsupported skia verb in skpath!");
+                path::Verb::Line => {
+                    cur_contour.push(Segment::Line(Line::from_points(points[0], points[1])));
+                }
+
+                path::Verb::Quad => {
+                    cur_contour.push(Segment::Quad(QuadBezier::from_points(
+                        points[0], points[1], points[2],
+                    )));
+                }
+
+                // segments have no conic variant, so these come in as cubics
+                path::Verb::Conic => {
+                    let weight = iter.conic_weight().unwrap_or(1.) as f64;
+                    let conic = Conic::from_points(points[0], points[1], points[2], weight);
+                    cur_contour.extend(
+                        conic
+                            .to_cubics(CONIC_TOLERANCE)
+                            .into_iter()
+                            .map(Segment::Cubic),
+                    );
+                }
+
+                path::Verb::Cubic => {
+                    cur_contour.push(Segment::Cubic(Bezier::from_points(
+                        points[0], points[1], points[2], points[3],
+                    )));
                 }
+
+                // closing a contour draws a line back to where it started if it isn't there yet
+                path::Verb::Close => {
+                    if let (Some(first), Some(last)) = (cur_contour.first(), cur_contour.last()) {
+                        let (start, end) = (first.start_point(), last.end_point());
+                        if start != end {
+                            cur_contour.push(Segment::Line(Line::from_points(end, start)));
+                        }
+
+                        contours.push(Piecewise::new(cur_contour, None));
+                    }
+
+                    cur_contour = Vec::new();
+                }
+
+                path::Verb::Done => break,
             }
         }
 
@@ -0,0 +1,321 @@
+use super::QuadBezier;
+use crate::error::{MathError, MathResult};
+use crate::subdivide::Subdivide;
+use crate::{Bezier, Piecewise, Vector};
+
+// Tangents whose cross product is smaller than this are considered parallel.
+const PARALLEL_EPSILON: f64 = 1e-9;
+
+// The most quads a single cubic gets replaced with before we give up.
+const MAX_QUADS: usize = 100;
+
+// Conversion of cubics to runs of quadratics in the spirit of fontTools' cu2qu. A cubic gets split
+// into n equal pieces and every piece is replaced by the quad whose handle sits where the piece's
+// end tangents meet. The on curve points between the quads are placed halfway between their
+// handles, which keeps the run smooth and matches TrueType's implied on curve points.
+//
+// Errors are measured parametrically between the cubic and the quads raised to cubics, which is
+// never smaller than the actual distance between the curves.
+impl QuadBezier {
+    /// Approximates the cubic with at least min_quads quadratics so that no point on the quads is
+    /// further than tolerance from the cubic.
+    pub fn from_cubic(bez: &Bezier, tolerance: f64, min_quads: usize) -> Vec<QuadBezier> {
+        QuadBezier::try_from_cubic(bez, tolerance, min_quads)
+            .unwrap_or_else(|err| panic!("Couldn't convert the cubic to quadratics: {}", err))
+    }
+
+    /// Fallible version of from_cubic, see [`QuadBezier::from_compatible_cubics`] for the errors.
+    pub fn try_from_cubic(
+        bez: &Bezier,
+        tolerance: f64,
+        min_quads: usize,
+    ) -> MathResult<Vec<QuadBezier>> {
+        let mut quads =
+            QuadBezier::from_compatible_cubics(std::slice::from_ref(bez), tolerance, min_quads)?;
+
+        Ok(quads.pop().unwrap())
+    }
+
+    /// Converts the same cubic from several masters of a design, using the same number of quads
+    /// for every master so that the results stay interpolation compatible.
+    ///
+    /// The tolerance has to be finite and above zero and the control points finite. If 100 quads
+    /// per cubic (or min_quads, if that's more) aren't enough we return [`MathError::NoFit`].
+    pub fn from_compatible_cubics(
+        cubics: &[Bezier],
+        tolerance: f64,
+        min_quads: usize,
+    ) -> MathResult<Vec<Vec<QuadBezier>>> {
+        if !tolerance.is_finite() || tolerance <= 0. {
+            return Err(MathError::InvalidTolerance(tolerance));
+        }
+        let finite = |point: &Vector| point.x.is_finite() && point.y.is_finite();
+        if !cubics
+            .iter()
+            .all(|bez| bez.to_control_points().iter().all(finite))
+        {
+            return Err(MathError::NonFinite);
+        }
+
+        let min_quads = usize::max(min_quads, 1);
+        for n in min_quads..=usize::max(min_quads, MAX_QUADS) {
+            let fits: Option<Vec<Vec<QuadBezier>>> = cubics
+                .iter()
+                .map(|bez| QuadBezier::fit_cubic(bez, n, tolerance))
+                .collect();
+
+            if let Some(fits) = fits {
+                return Ok(fits);
+            }
+        }
+
+        Err(MathError::NoFit)
+    }
+
+    // Approximates the cubic with exactly n quads, if that's possible within tolerance.
+    fn fit_cubic(bez: &Bezier, n: usize, tolerance: f64) -> Option<Vec<QuadBezier>> {
+        if let Some(quads) = QuadBezier::smooth_spline(bez, n) {
+            if quads_fit_cubic(bez, &quads, tolerance) {
+                return Some(quads);
+            }
+        }
+
+        // When the tangents can't be intersected (colocated handles, straight pieces) or the smooth
+        // spline strays too far we give up on smoothness and match each piece on its own. The
+        // handle (3(p1 + p2) - (p0 + p3)) / 4 leaves an error of exactly sqrt(3)/36 times the
+        // length of the piece's cubic power coefficient, which shrinks with n^3.
+        let [a, _, _, _] = bez.power_coefficients();
+        let error = 3f64.sqrt() / 36. * a.magnitude() / (n * n * n) as f64;
+        if error > tolerance {
+            return None;
+        }
+
+        let quads = split_evenly(bez, n)
+            .iter()
+            .map(|piece| {
+                let [p0, p1, p2, p3] = piece.to_control_points();
+                QuadBezier::from_points(p0, ((p1 + p2) * 3. - (p0 + p3)) / 4., p3)
+            })
+            .collect();
+
+        Some(quads)
+    }
+
+    fn smooth_spline(bez: &Bezier, n: usize) -> Option<Vec<QuadBezier>> {
+        let pieces = split_evenly(bez, n);
+
+        let mut handles = Vec::new();
+        for piece in &pieces {
+            let [p0, p1, p2, p3] = piece.to_control_points();
+            handles.push(intersect_tangents(p0, p1 - p0, p3, p3 - p2)?);
+        }
+
+        let mut on_curve = vec![bez.w1];
+        for pair in handles.windows(2) {
+            on_curve.push(pair[0].lerp(pair[1], 0.5));
+        }
+        on_curve.push(bez.w4);
+
+        let quads = handles
+            .iter()
+            .enumerate()
+            .map(|(i, handle)| QuadBezier::from_points(on_curve[i], *handle, on_curve[i + 1]))
+            .collect();
+
+        Some(quads)
+    }
+}
+
+fn split_evenly(bez: &Bezier, n: usize) -> Vec<Bezier> {
+    let t_values = (1..n).map(|i| i as f64 / n as f64).collect();
+
+    bez.split_at_multiple_t(t_values)
+}
+
+fn intersect_tangents(p: Vector, dp: Vector, q: Vector, dq: Vector) -> Option<Vector> {
+    let denominator = dp.cross(dq);
+    if denominator.abs() <= PARALLEL_EPSILON * dp.magnitude() * dq.magnitude() {
+        return None;
+    }
+
+    Some(p + dp * ((q - p).cross(dq) / denominator))
+}
+
+fn quads_fit_cubic(bez: &Bezier, quads: &[QuadBezier], tolerance: f64) -> bool {
+    split_evenly(bez, quads.len())
+        .iter()
+        .zip(quads)
+        .all(|(piece, quad)| {
+            let elevated = quad.to_cubic();
+
+            farthest_fit_inside(
+                elevated.w1 - piece.w1,
+                elevated.w2 - piece.w2,
+                elevated.w3 - piece.w3,
+                elevated.w4 - piece.w4,
+                tolerance,
+            )
+        })
+}
+
+// Checks whether the cubic with control points d0..d3 stays within tolerance of the origin, by
+// bisecting it until its control polygon does.
+fn farthest_fit_inside(d0: Vector, d1: Vector, d2: Vector, d3: Vector, tolerance: f64) -> bool {
+    if d0.magnitude() > tolerance || d3.magnitude() > tolerance {
+        return false;
+    }
+    if d1.magnitude() <= tolerance && d2.magnitude() <= tolerance {
+        return true;
+    }
+
+    let middle = (d0 + (d1 + d2) * 3. + d3) / 8.;
+    if middle.magnitude() > tolerance {
+        return false;
+    }
+
+    let derivative = (d3 + d2 - d1 - d0) / 8.;
+    farthest_fit_inside(d0, (d0 + d1) / 2., middle - derivative, middle, tolerance)
+        && farthest_fit_inside(middle, middle + derivative, (d2 + d3) / 2., d3, tolerance)
+}
+
+impl Piecewise<Bezier> {
+    /// Converts every cubic to quadratics, see [`QuadBezier::from_cubic`]. Each cubic's cut range
+    /// is shared evenly between the quads that replace it.
+    pub fn to_quadratic(&self, tolerance: f64, min_quads: usize) -> Piecewise<QuadBezier> {
+        self.try_to_quadratic(tolerance, min_quads)
+            .unwrap_or_else(|err| panic!("Couldn't convert the contour to quadratics: {}", err))
+    }
+
+    /// Fallible version of to_quadratic.
+    pub fn try_to_quadratic(
+        &self,
+        tolerance: f64,
+        min_quads: usize,
+    ) -> MathResult<Piecewise<QuadBezier>> {
+        let mut converted = Piecewise::<Bezier>::to_compatible_quadratics(
+            std::slice::from_ref(self),
+            tolerance,
+            min_quads,
+        )?;
+
+        Ok(converted.pop().unwrap())
+    }
+
+    /// Converts the same contour from several masters so that every master ends up with the same
+    /// number of quads. Masters that don't have the same number of segments give
+    /// [`MathError::IncompatibleMasters`], and a segment that can't be converted is reported with
+    /// its index.
+    pub fn to_compatible_quadratics(
+        masters: &[Piecewise<Bezier>],
+        tolerance: f64,
+        min_quads: usize,
+    ) -> MathResult<Vec<Piecewise<QuadBezier>>> {
+        let seg_count = match masters.first() {
+            Some(master) => master.segs.len(),
+            None => return Ok(Vec::new()),
+        };
+        if masters.iter().any(|master| master.segs.len() != seg_count) {
+            return Err(MathError::IncompatibleMasters);
+        }
+
+        let mut segs: Vec<Vec<QuadBezier>> = vec![Vec::new(); masters.len()];
+        let mut cuts: Vec<Vec<f64>> = masters.iter().map(|master| vec![master.cuts[0]]).collect();
+
+        for i in 0..seg_count {
+            let cubics: Vec<Bezier> = masters
+                .iter()
+                .map(|master| master.segs[i].clone())
+                .collect();
+            let quads = QuadBezier::from_compatible_cubics(&cubics, tolerance, min_quads)
+                .map_err(MathError::in_segment(i))?;
+
+            for (m, master_quads) in quads.into_iter().enumerate() {
+                let n = master_quads.len();
+                for k in 1..=n {
+                    cuts[m].push(masters[m].global_t(i, k as f64 / n as f64));
+                }
+                segs[m].extend(master_quads);
+            }
+        }
+
+        Ok(segs
+            .into_iter()
+            .zip(cuts)
+            .map(|(segs, cuts)| Piecewise::new(segs, Some(cuts)))
+            .collect())
+    }
+}
+
+impl Piecewise<Piecewise<Bezier>> {
+    pub fn to_quadratic(
+        &self,
+        tolerance: f64,
+        min_quads: usize,
+    ) -> Piecewise<Piecewise<QuadBezier>> {
+        self.try_to_quadratic(tolerance, min_quads)
+            .unwrap_or_else(|err| panic!("Couldn't convert the outline to quadratics: {}", err))
+    }
+
+    /// Fallible version of to_quadratic, errors carry the index of the contour they came from.
+    pub fn try_to_quadratic(
+        &self,
+        tolerance: f64,
+        min_quads: usize,
+    ) -> MathResult<Piecewise<Piecewise<QuadBezier>>> {
+        let new_segments = self
+            .segs
+            .iter()
+            .enumerate()
+            .map(|(i, contour)| {
+                contour
+                    .try_to_quadratic(tolerance, min_quads)
+                    .map_err(MathError::in_segment(i))
+            })
+            .collect::<MathResult<_>>()?;
+
+        Ok(Piecewise::new(new_segments, Some(self.cuts.clone())))
+    }
+
+    /// Outline version of [`Piecewise::to_compatible_quadratics`], the masters need to have the
+    /// same number of contours with the same number of segments.
+    pub fn to_compatible_quadratics(
+        masters: &[Piecewise<Piecewise<Bezier>>],
+        tolerance: f64,
+        min_quads: usize,
+    ) -> MathResult<Vec<Piecewise<Piecewise<QuadBezier>>>> {
+        let contour_count = match masters.first() {
+            Some(master) => master.segs.len(),
+            None => return Ok(Vec::new()),
+        };
+        if masters
+            .iter()
+            .any(|master| master.segs.len() != contour_count)
+        {
+            return Err(MathError::IncompatibleMasters);
+        }
+
+        let mut contours: Vec<Vec<Piecewise<QuadBezier>>> = vec![Vec::new(); masters.len()];
+        for i in 0..contour_count {
+            let contour_masters: Vec<Piecewise<Bezier>> = masters
+                .iter()
+                .map(|master| master.segs[i].clone())
+                .collect();
+            let converted = Piecewise::<Bezier>::to_compatible_quadratics(
+                &contour_masters,
+                tolerance,
+                min_quads,
+            )
+            .map_err(MathError::in_segment(i))?;
+
+            for (m, contour) in converted.into_iter().enumerate() {
+                contours[m].push(contour);
+            }
+        }
+
+        Ok(contours
+            .into_iter()
+            .zip(masters)
+            .map(|(contours, master)| Piecewise::new(contours, Some(master.cuts.clone())))
+            .collect())
+    }
+}
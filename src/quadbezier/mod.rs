@@ -1,7 +1,10 @@
 use flo_curves::bezier::{de_casteljau2, de_casteljau3, derivative3};
 use glifparser::{glif::point::quad::QPoint, PointData};
 
-use crate::{subdivide::Subdivide, Evaluate, Rect, Vector};
+use crate::roots::{interior_roots, solve_linear};
+use crate::{subdivide::Subdivide, Bezier, Evaluate, Rect, Vector};
+
+mod from_cubic;
 
 #[derive(Clone, Debug)]
 #[allow(non_snake_case)]
@@ -33,6 +36,42 @@ impl QuadBezier {
         [self.w1.clone(), self.w2.clone(), self.w3.clone()]
     }
 
+    pub fn reverse(&self) -> Self {
+        QuadBezier::from_points(self.w3, self.w2, self.w1)
+    }
+
+    // Degree elevation. The cubic traces exactly the same curve with exactly the same parameterization.
+    pub fn to_cubic(&self) -> Bezier {
+        Bezier::from_points(
+            self.w1,
+            self.w1.lerp(self.w2, 2. / 3.),
+            self.w3.lerp(self.w2, 2. / 3.),
+            self.w3,
+        )
+    }
+
+    // Returns the coefficients [a, b, c] of the power basis form of the curve,
+    // B(t) = a*t^2 + b*t + c
+    pub fn power_coefficients(&self) -> [Vector; 3] {
+        [
+            self.w1 - self.w2 * 2. + self.w3,
+            (self.w2 - self.w1) * 2.,
+            self.w1,
+        ]
+    }
+
+    /// Returns the t values where the derivative of x and the derivative of y vanish, in that
+    /// order. As with cubics only extrema strictly inside the curve are reported.
+    pub fn extrema(&self) -> (Vec<f64>, Vec<f64>) {
+        let [a, b, _] = self.power_coefficients();
+
+        // B'(t) = 2a*t + b
+        let x_extrema = interior_roots(solve_linear(2. * a.x, b.x));
+        let y_extrema = interior_roots(solve_linear(2. * a.y, b.y));
+
+        (x_extrema, y_extrema)
+    }
+
     pub fn calc_line_intersection(&self, line_start: Vector, line_end: Vector) -> Vec<Vector> {
         let mut intersections = Vec::new();
 
@@ -137,6 +176,10 @@ impl Evaluate for QuadBezier {
         tangent
     }
 
+    fn second_derivative_at(&self, _t: f64) -> Vector {
+        (self.w1 - self.w2 * 2. + self.w3) * 2.
+    }
+
     fn apply_transform<F>(&self, transform: F) -> Self
     where
         F: Fn(&Vector) -> Vector,
@@ -155,8 +198,14 @@ impl Evaluate for QuadBezier {
     }
 
     fn bounds(&self) -> Rect {
-        // Assuming to_control_points_vec is available in your code
-        Rect::AABB_from_points(vec![self.w1.clone(), self.w2.clone(), self.w3.clone()])
+        let (x_extrema, y_extrema) = self.extrema();
+
+        let mut points = vec![self.w1.clone(), self.w3.clone()];
+        for t in x_extrema.into_iter().chain(y_extrema) {
+            points.push(self.at(t));
+        }
+
+        Rect::AABB_from_points(points)
     }
 
     fn start_point(&self) -> Vector {
@@ -174,7 +223,27 @@ impl Subdivide for QuadBezier {
             return None;
         }
 
-        // Perform De Casteljau's algorithm to split the curve at t
+        Some(self.de_casteljau(t))
+    }
+
+    // Two splits at most, the same as a cubic's.
+    fn subsegment(&self, t0: f64, t1: f64) -> QuadBezier {
+        let head = if t1 < 1. {
+            self.de_casteljau(t1).0
+        } else {
+            self.clone()
+        };
+        if t0 <= 0. || t1 <= 0. {
+            return head;
+        }
+
+        head.de_casteljau(t0 / t1.min(1.)).1
+    }
+}
+
+impl QuadBezier {
+    // Perform De Casteljau's algorithm to split the curve at t
+    fn de_casteljau(&self, t: f64) -> (QuadBezier, QuadBezier) {
         let q1 = self.w1.lerp(self.w2, t);
         let q2 = self.w2.lerp(self.w3, t);
 
@@ -183,6 +252,6 @@ impl Subdivide for QuadBezier {
         let first_half = QuadBezier::from_points(self.w1.clone(), q1, r1);
         let second_half = QuadBezier::from_points(r1, q2, self.w3.clone());
 
-        Some((first_half, second_half))
+        (first_half, second_half)
     }
 }
@@ -0,0 +1,68 @@
+use crate::conic::Conic;
+use crate::ellipticalarc::EllipticalArc;
+use crate::line::Line;
+use crate::quadbezier::QuadBezier;
+use crate::segment::Segment;
+use crate::{Bezier, Evaluate, Piecewise};
+
+/// Running a path backwards. The reversed path traces the same points, so at(t) of the reversed
+/// curve is at(1 - t) of the original.
+pub trait Reverse {
+    fn reverse(&self) -> Self;
+}
+
+impl Reverse for Bezier {
+    fn reverse(&self) -> Self {
+        Bezier::reverse(self)
+    }
+}
+
+impl Reverse for QuadBezier {
+    fn reverse(&self) -> Self {
+        QuadBezier::reverse(self)
+    }
+}
+
+impl Reverse for Line {
+    fn reverse(&self) -> Self {
+        Line::reverse(self)
+    }
+}
+
+impl Reverse for Segment {
+    fn reverse(&self) -> Self {
+        Segment::reverse(self)
+    }
+}
+
+impl Reverse for Conic {
+    fn reverse(&self) -> Self {
+        Conic::reverse(self)
+    }
+}
+
+impl Reverse for EllipticalArc {
+    fn reverse(&self) -> Self {
+        EllipticalArc::reverse(self)
+    }
+}
+
+// The cuts get mirrored within the range they cover, so every segment keeps the span of t it had
+// and at(t) of the result is at(first cut + last cut - t) of the original. For an outline this
+// reverses the order of the contours as well as each contour.
+impl<T: Evaluate + Reverse> Reverse for Piecewise<T> {
+    fn reverse(&self) -> Self {
+        let segs = self.segs.iter().rev().map(Reverse::reverse).collect();
+        let cuts = match (self.cuts.first(), self.cuts.last()) {
+            (Some(first), Some(last)) => self
+                .cuts
+                .iter()
+                .rev()
+                .map(|cut| first + last - cut)
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Piecewise::new(segs, Some(cuts))
+    }
+}
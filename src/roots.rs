@@ -0,0 +1,226 @@
+// Real root finding for the polynomials that show up when working with quadratic and cubic
+// beziers. Coefficients are passed highest power first, so
+// solve_quadratic(a, b, c) solves a*t^2 + b*t + c = 0.
+
+// Coefficients smaller than this (relative to the largest coefficient) are treated as zero, which
+// lets a cubic whose leading term vanishes fall back to the quadratic solver and so on.
+const DEGENERATE_EPSILON: f64 = 1e-12;
+
+fn max_magnitude(coefficients: &[f64]) -> f64 {
+    coefficients.iter().fold(0., |acc: f64, c| acc.max(c.abs()))
+}
+
+/// Returns the root of a*t + b = 0, or nothing if the equation is degenerate.
+pub(crate) fn solve_linear(a: f64, b: f64) -> Vec<f64> {
+    let scale = max_magnitude(&[a, b]);
+    if scale == 0. || a.abs() <= scale * DEGENERATE_EPSILON {
+        return vec![];
+    }
+
+    vec![-b / a]
+}
+
+/// Returns the real roots of a*t^2 + b*t + c = 0 in ascending order. A double root is returned once.
+pub(crate) fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    let scale = max_magnitude(&[a, b, c]);
+    if scale == 0. {
+        return vec![];
+    }
+    if a.abs() <= scale * DEGENERATE_EPSILON {
+        return solve_linear(b, c);
+    }
+
+    let discriminant = b * b - 4. * a * c;
+    if discriminant.abs() <= b * b * DEGENERATE_EPSILON {
+        return vec![-b / (2. * a)];
+    }
+    if discriminant < 0. {
+        return vec![];
+    }
+
+    // https://people.csail.mit.edu/bkph/articles/Quadratics.pdf
+    // this form avoids the catastrophic cancellation of the schoolbook formula
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    let mut roots = if q == 0. {
+        vec![0.]
+    } else {
+        vec![q / a, c / q]
+    };
+
+    roots.sort_by(|a, b| a.total_cmp(b));
+    roots
+}
+
+/// Keeps only the roots that lie strictly between 0 and 1.
+pub(crate) fn interior_roots(roots: Vec<f64>) -> Vec<f64> {
+    roots.into_iter().filter(|t| *t > 0. && *t < 1.).collect()
+}
+
+/// Returns the real roots of a*t^3 + b*t^2 + c*t + d = 0 in ascending order. Repeated roots are
+/// returned once.
+pub(crate) fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    let scale = max_magnitude(&[a, b, c, d]);
+    if scale == 0. {
+        return vec![];
+    }
+    if a.abs() <= scale * DEGENERATE_EPSILON {
+        return solve_quadratic(b, c, d);
+    }
+
+    // https://mathworld.wolfram.com/CubicFormula.html
+    // we work on the monic form t^3 + a2*t^2 + a1*t + a0
+    let a2 = b / a;
+    let a1 = c / a;
+    let a0 = d / a;
+
+    let q = (3. * a1 - a2 * a2) / 9.;
+    let r = (9. * a2 * a1 - 27. * a0 - 2. * a2 * a2 * a2) / 54.;
+    let discriminant = q * q * q + r * r;
+    let shift = -a2 / 3.;
+
+    let mut roots = if discriminant.abs() <= (q.abs().powi(3) + r * r) * DEGENERATE_EPSILON {
+        // a double (or triple) root
+        let s = r.cbrt();
+        vec![shift + 2. * s, shift - s]
+    } else if discriminant > 0. {
+        let sqrt_discriminant = discriminant.sqrt();
+        let s = (r + sqrt_discriminant).cbrt();
+        let t = (r - sqrt_discriminant).cbrt();
+        vec![shift + s + t]
+    } else {
+        // three distinct real roots, use the trigonometric form
+        let theta = f64::acos((r / (-q * q * q).sqrt()).clamp(-1., 1.));
+        let m = 2. * (-q).sqrt();
+        vec![
+            shift + m * f64::cos(theta / 3.),
+            shift + m * f64::cos((theta + 2. * std::f64::consts::PI) / 3.),
+            shift + m * f64::cos((theta + 4. * std::f64::consts::PI) / 3.),
+        ]
+    };
+
+    // the closed form loses a few digits, a couple of newton steps get them back
+    let f = |t: f64| ((a * t + b) * t + c) * t + d;
+    let df = |t: f64| (3. * a * t + 2. * b) * t + c;
+    for root in roots.iter_mut() {
+        for _ in 0..2 {
+            let slope = df(*root);
+            if slope == 0. {
+                break;
+            }
+            // near a double root the slope vanishes too, so only take steps that actually help
+            let polished = *root - f(*root) / slope;
+            if f(polished).abs() >= f(*root).abs() {
+                break;
+            }
+            *root = polished;
+        }
+    }
+
+    roots.sort_by(|a, b| a.total_cmp(b));
+    roots.dedup_by(|a, b| (*a - *b).abs() <= DEGENERATE_EPSILON.sqrt());
+    roots
+}
+
+/// Keeps the roots that lie within eps of the unit interval, clamping them into it.
+pub(crate) fn unit_interval_roots(roots: Vec<f64>, eps: f64) -> Vec<f64> {
+    roots
+        .into_iter()
+        .filter(|t| *t >= -eps && *t <= 1. + eps)
+        .map(|t| t.clamp(0., 1.))
+        .collect()
+}
+
+fn evaluate_polynomial(coefficients: &[f64], t: f64) -> f64 {
+    coefficients.iter().fold(0., |acc, c| acc * t + c)
+}
+
+fn derivative(coefficients: &[f64]) -> Vec<f64> {
+    let degree = coefficients.len() - 1;
+    coefficients[..degree]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| c * (degree - i) as f64)
+        .collect()
+}
+
+/// Returns the real roots of an arbitrary polynomial that lie within [low, high] in ascending
+/// order. Coefficients are highest power first like the rest of this module.
+///
+/// Anything above a cubic has no usable closed form, so we find the roots of the derivative first.
+/// They split the range into pieces on which the polynomial is monotonic, and each piece can hold
+/// at most one root which we find by bisection.
+pub(crate) fn solve_polynomial(coefficients: &[f64], low: f64, high: f64) -> Vec<f64> {
+    let scale = max_magnitude(coefficients);
+    if scale == 0. {
+        return vec![];
+    }
+
+    // strip vanishing leading terms so the degree is honest
+    let first = coefficients
+        .iter()
+        .position(|c| c.abs() > scale * DEGENERATE_EPSILON)
+        .unwrap();
+    let coefficients = &coefficients[first..];
+
+    let roots = match coefficients.len() {
+        1 => vec![],
+        2 => solve_linear(coefficients[0], coefficients[1]),
+        3 => solve_quadratic(coefficients[0], coefficients[1], coefficients[2]),
+        4 => solve_cubic(
+            coefficients[0],
+            coefficients[1],
+            coefficients[2],
+            coefficients[3],
+        ),
+        _ => {
+            let mut bounds = vec![low];
+            bounds.extend(solve_polynomial(&derivative(coefficients), low, high));
+            bounds.push(high);
+
+            let f = |t: f64| evaluate_polynomial(coefficients, t);
+            let tolerance = scale * DEGENERATE_EPSILON;
+
+            let mut roots = vec![];
+            for pair in bounds.windows(2) {
+                let (mut a, mut b) = (pair[0], pair[1]);
+                let (f_a, f_b) = (f(a), f(b));
+
+                // a critical point that touches zero is a repeated root
+                if f_a.abs() <= tolerance {
+                    roots.push(a);
+                    continue;
+                }
+                if f_a.signum() == f_b.signum() {
+                    continue;
+                }
+
+                for _ in 0..128 {
+                    let middle = (a + b) / 2.;
+                    if middle == a || middle == b {
+                        break;
+                    }
+                    if f(middle).signum() == f_a.signum() {
+                        a = middle;
+                    } else {
+                        b = middle;
+                    }
+                }
+                roots.push((a + b) / 2.);
+            }
+
+            if f(high).abs() <= tolerance {
+                roots.push(high);
+            }
+
+            roots
+        }
+    };
+
+    let mut roots: Vec<f64> = roots
+        .into_iter()
+        .filter(|t| (low..=high).contains(t))
+        .collect();
+    roots.sort_by(|a, b| a.total_cmp(b));
+    roots.dedup_by(|a, b| (*a - *b).abs() <= DEGENERATE_EPSILON.sqrt());
+    roots
+}
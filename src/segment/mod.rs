@@ -0,0 +1,201 @@
+use crate::intersection::Intersect;
+use crate::line::Line;
+use crate::nearest::NearestPoint;
+use crate::quadbezier::QuadBezier;
+use crate::{subdivide::Subdivide, Bezier, Evaluate, Piecewise, Rect, Vector};
+
+// A single piece of a path that can mix lines, quadratics and cubics. Piecewise<Segment> keeps
+// track of what kind each piece is, which Piecewise<Bezier> can't.
+#[derive(Clone, Debug)]
+pub enum Segment {
+    Line(Line),
+    Quad(QuadBezier),
+    Cubic(Bezier),
+}
+
+impl Segment {
+    pub fn to_cubic(&self) -> Bezier {
+        match self {
+            Segment::Line(line) => line.to_cubic(),
+            Segment::Quad(quad) => quad.to_cubic(),
+            Segment::Cubic(bez) => bez.clone(),
+        }
+    }
+
+    pub fn reverse(&self) -> Self {
+        match self {
+            Segment::Line(line) => Segment::Line(line.reverse()),
+            Segment::Quad(quad) => Segment::Quad(quad.reverse()),
+            Segment::Cubic(bez) => Segment::Cubic(bez.reverse()),
+        }
+    }
+}
+
+impl From<Line> for Segment {
+    fn from(line: Line) -> Self {
+        Segment::Line(line)
+    }
+}
+
+impl From<QuadBezier> for Segment {
+    fn from(quad: QuadBezier) -> Self {
+        Segment::Quad(quad)
+    }
+}
+
+impl From<Bezier> for Segment {
+    fn from(bez: Bezier) -> Self {
+        Segment::Cubic(bez)
+    }
+}
+
+impl Evaluate for Segment {
+    fn at(&self, t: f64) -> Vector {
+        match self {
+            Segment::Line(line) => line.at(t),
+            Segment::Quad(quad) => quad.at(t),
+            Segment::Cubic(bez) => bez.at(t),
+        }
+    }
+
+    fn tangent_at(&self, t: f64) -> Vector {
+        match self {
+            Segment::Line(line) => line.tangent_at(t),
+            Segment::Quad(quad) => quad.tangent_at(t),
+            Segment::Cubic(bez) => bez.tangent_at(t),
+        }
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector {
+        match self {
+            Segment::Line(line) => line.second_derivative_at(t),
+            Segment::Quad(quad) => quad.second_derivative_at(t),
+            Segment::Cubic(bez) => bez.second_derivative_at(t),
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        match self {
+            Segment::Line(line) => line.bounds(),
+            Segment::Quad(quad) => quad.bounds(),
+            Segment::Cubic(bez) => bez.bounds(),
+        }
+    }
+
+    fn apply_transform<F: Send + Sync>(&self, transform: F) -> Self
+    where
+        F: Fn(&Vector) -> Vector,
+    {
+        match self {
+            Segment::Line(line) => Segment::Line(line.apply_transform(transform)),
+            Segment::Quad(quad) => Segment::Quad(quad.apply_transform(transform)),
+            Segment::Cubic(bez) => Segment::Cubic(bez.apply_transform(transform)),
+        }
+    }
+
+    fn start_point(&self) -> Vector {
+        match self {
+            Segment::Line(line) => line.start_point(),
+            Segment::Quad(quad) => quad.start_point(),
+            Segment::Cubic(bez) => bez.start_point(),
+        }
+    }
+
+    fn end_point(&self) -> Vector {
+        match self {
+            Segment::Line(line) => line.end_point(),
+            Segment::Quad(quad) => quad.end_point(),
+            Segment::Cubic(bez) => bez.end_point(),
+        }
+    }
+}
+
+impl Subdivide for Segment {
+    fn split(&self, t: f64) -> Option<(Segment, Segment)> {
+        match self {
+            Segment::Line(line) => line
+                .split(t)
+                .map(|(a, b)| (Segment::Line(a), Segment::Line(b))),
+            Segment::Quad(quad) => quad
+                .split(t)
+                .map(|(a, b)| (Segment::Quad(a), Segment::Quad(b))),
+            Segment::Cubic(bez) => bez
+                .split(t)
+                .map(|(a, b)| (Segment::Cubic(a), Segment::Cubic(b))),
+        }
+    }
+
+    fn subsegment(&self, t0: f64, t1: f64) -> Segment {
+        match self {
+            Segment::Line(line) => Segment::Line(line.subsegment(t0, t1)),
+            Segment::Quad(quad) => Segment::Quad(quad.subsegment(t0, t1)),
+            Segment::Cubic(bez) => Segment::Cubic(bez.subsegment(t0, t1)),
+        }
+    }
+}
+
+impl NearestPoint for Segment {
+    fn nearest_point(&self, point: Vector) -> (f64, Vector, f64) {
+        match self {
+            Segment::Line(line) => line.nearest_point(point),
+            Segment::Quad(quad) => quad.nearest_point(point),
+            Segment::Cubic(bez) => bez.nearest_point(point),
+        }
+    }
+
+    fn nearest_points_within(&self, point: Vector, radius: f64) -> Vec<(f64, Vector, f64)> {
+        match self {
+            Segment::Line(line) => line.nearest_points_within(point, radius),
+            Segment::Quad(quad) => quad.nearest_points_within(point, radius),
+            Segment::Cubic(bez) => bez.nearest_points_within(point, radius),
+        }
+    }
+}
+
+// Lines and quads raise to cubics without changing their parameterization, so the t values the
+// cubic intersection finds hold for the original segments too.
+impl Intersect for Segment {
+    fn intersections(&self, other: &Segment) -> Vec<(f64, f64, Vector)> {
+        self.to_cubic().intersections(&other.to_cubic())
+    }
+}
+
+impl From<&Piecewise<Bezier>> for Piecewise<Segment> {
+    /// Cubics that are really straight lines become line segments.
+    fn from(pw: &Piecewise<Bezier>) -> Self {
+        let segs = pw
+            .segs
+            .iter()
+            .map(|bez| match Line::from_cubic(bez) {
+                Some(line) => Segment::Line(line),
+                None => Segment::Cubic(bez.clone()),
+            })
+            .collect();
+
+        Piecewise::new(segs, Some(pw.cuts.clone()))
+    }
+}
+
+impl From<&Piecewise<Piecewise<Bezier>>> for Piecewise<Piecewise<Segment>> {
+    fn from(outline: &Piecewise<Piecewise<Bezier>>) -> Self {
+        let segs = outline.segs.iter().map(Piecewise::from).collect();
+
+        Piecewise::new(segs, Some(outline.cuts.clone()))
+    }
+}
+
+impl Piecewise<Segment> {
+    pub fn to_cubic(&self) -> Piecewise<Bezier> {
+        let segs = self.segs.iter().map(|seg| seg.to_cubic()).collect();
+
+        Piecewise::new(segs, Some(self.cuts.clone()))
+    }
+}
+
+impl Piecewise<Piecewise<Segment>> {
+    pub fn to_cubic(&self) -> Piecewise<Piecewise<Bezier>> {
+        let segs = self.segs.iter().map(|contour| contour.to_cubic()).collect();
+
+        Piecewise::new(segs, Some(self.cuts.clone()))
+    }
+}
@@ -1,8 +1,37 @@
+use crate::evaluate::Evaluate;
+
 pub trait Subdivide {
     fn split(&self, t: f64) -> Option<(Self, Self)>
     where
         Self: Sized;
 
+    /// The part of the curve between t0 and t1, reparameterized to 0-1. t0 should come before t1.
+    /// By default the end is split off first and then the start off what's left. If t1 isn't
+    /// after both t0 and the start there's nothing between them, and you get the curve collapsed
+    /// onto the point at t0 (or at the start if t0 is before it).
+    fn subsegment(&self, t0: f64, t1: f64) -> Self
+    where
+        Self: Sized + Clone + Evaluate,
+    {
+        if t1 <= t0 || t1 <= 0. {
+            let point = self.at(t0.clamp(0., 1.));
+            return self.apply_transform(|_| point);
+        }
+
+        let head = match self.split(t1) {
+            Some((head, _)) => head,
+            None => self.clone(),
+        };
+        if t0 <= 0. {
+            return head;
+        }
+
+        match head.split(t0 / t1) {
+            Some((_, part)) => part,
+            None => head,
+        }
+    }
+
     fn split_at_multiple_t(&self, mut t_values: Vec<f64>) -> Vec<Self>
     where
         Self: Sized + Clone,
@@ -1,7 +1,6 @@
-use std::collections::VecDeque;
-
-use super::consts::SMALL_DISTANCE;
-use super::{Bezier, Evaluate, GlyphBuilder, Piecewise, Vector};
+use super::error::{MathError, MathResult};
+use super::glyphbuilder::fix_path;
+use super::{Bezier, Evaluate, GlyphBuilder, Piecewise};
 use glifparser::glif::contour_operations::vws::{
     CapType, InterpolationType, VWSContour, VWSHandle,
 };
@@ -17,7 +16,7 @@ pub struct VWSSettings<PD: PointData> {
 // we want to deal with colocated handles here so that we don't get funky results at caps and joins
 // where one or more handles is colocated
 fn preprocess_path(in_pw: &Piecewise<Bezier>) -> Piecewise<Bezier> {
-    let in_pw = in_pw.remove_short_segs(0.01, 3);
+    let in_pw = in_pw.remove_short_segs_within(0.01, 0.0001);
     let mut out_contours = Vec::new();
 
     for bez in &in_pw.segs {
@@ -40,92 +39,38 @@ fn preprocess_path(in_pw: &Piecewise<Bezier>) -> Piecewise<Bezier> {
         cuts: in_pw.cuts.clone(),
     };
 }
-// takes a vector of beziers and fills in discontinuities with joins
-fn fix_path(in_path: GlyphBuilder, closed: bool, join_type: JoinType) -> GlyphBuilder {
-    let mut out = GlyphBuilder::new();
-
-    let join_to = match join_type {
-        JoinType::Bevel => GlyphBuilder::bevel_to,
-        JoinType::Round => GlyphBuilder::arc_to,
-        JoinType::Circle => GlyphBuilder::circle_arc_to,
-        JoinType::Miter => GlyphBuilder::miter_to,
-    };
-
-    let mut path_iter = in_path.beziers.iter().peekable();
-
-    while let Some(bezier) = path_iter.next() {
-        if let Some(next_bezier) = path_iter.peek() {
-            let next_start = next_bezier.start_point();
-            let last_end = bezier.end_point();
-            if !last_end.is_near(next_start, SMALL_DISTANCE) {
-                // the end of our last curve doesn't match up with the start of our next so we need to
-                // deal with the discontinuity be creating a join
-                let from_end_point = bezier.at(1.);
-                let to_start_point = next_bezier.at(0.);
-
-                // used for round joins
-                let tangent1 = bezier.tangent_at(1.).normalize();
-                let tangent2 = next_bezier.tangent_at(0.).normalize();
-
-                let discontinuity_vec = to_start_point - from_end_point;
-                let dr = Vector {
-                    x: discontinuity_vec.y,
-                    y: -discontinuity_vec.x,
-                }
-                .normalize();
-
-                let t1_dot_dr = tangent1.dot(dr);
-                let t2_dot_dr = tangent2.dot(dr);
-                let tangent1 = if t1_dot_dr > 0. { tangent1 } else { -tangent1 };
-                let tangent2 = if t2_dot_dr < 0. { tangent2 } else { -tangent2 };
-
-                out.bezier_to(bezier.clone());
-                join_to(&mut out, next_start, tangent1, tangent2);
-            } else {
-                out.bezier_to(bezier.clone());
-            }
-        } else if closed {
-            // our path is closed and if there's not a next point we need to make sure that our current
-            // and last curve matches up with the first one
-
-            let first_bez = in_path.beziers.first().unwrap();
-            let first_point = first_bez.start_point();
-            let last_end = bezier.end_point();
-
-            if !last_end.is_near(first_point, SMALL_DISTANCE) {
-                let tangent1 = bezier.tangent_at(1.).normalize();
-                let tangent2 = first_bez.tangent_at(0.).normalize();
-                let discontinuity_vec = first_point - last_end;
-                let on_outside = Vector::dot(tangent2, discontinuity_vec) <= 0.;
-
-                if !on_outside {
-                    out.bezier_to(bezier.clone());
-                    join_to(&mut out, first_point, tangent1, tangent2);
-                } else {
-                    out.bezier_to(bezier.clone());
-                    out.line_to(first_point);
-                }
-            } else {
-                out.bezier_to(bezier.clone());
-            }
-        } else {
-            out.bezier_to(bezier.clone());
-        }
-    }
-
-    return out;
-}
-
 pub fn variable_width_stroke<PD: PointData>(
     in_pw: &Piecewise<Bezier>,
     vws_contour: &VWSContour,
     settings: &VWSSettings<PD>,
 ) -> Piecewise<Piecewise<Bezier>> {
+    try_variable_width_stroke(in_pw, vws_contour, settings)
+        .unwrap_or_else(|err| panic!("Couldn't stroke the contour: {}", err))
+}
+
+// Fallible version of variable_width_stroke. Fails on an empty contour, a contour with fewer
+// handles than it needs, a missing custom cap and segments that can't be offset.
+pub fn try_variable_width_stroke<PD: PointData>(
+    in_pw: &Piecewise<Bezier>,
+    vws_contour: &VWSContour,
+    settings: &VWSSettings<PD>,
+) -> MathResult<Piecewise<Piecewise<Bezier>>> {
+    // short segments get dropped here, which can leave us with nothing
     let in_pw = preprocess_path(in_pw);
+    if in_pw.segs.is_empty() {
+        return Err(MathError::EmptyPiecewise);
+    }
 
     let closed = in_pw.is_closed();
     let stroke_handles = &vws_contour.handles;
 
+    if stroke_handles.len() < in_pw.segs.len() + 1 {
+        return Err(MathError::MismatchedHandles {
+            handles: stroke_handles.len(),
+            segs: in_pw.segs.len(),
+        });
+    }
+
     // check if our input path is closed
     // We're gonna keep track of a left line and a right line.
     let mut left_line = GlyphBuilder::new();
@@ -192,7 +137,11 @@ pub fn variable_width_stroke<PD: PointData>(
             20,
             4.0,
         );
-        left_line.append_vec(left_offset.unwrap());
+        left_line.append_vec(
+            left_offset
+                .ok_or(MathError::OffsetFailed)
+                .map_err(MathError::in_segment(i))?,
+        );
 
         let right_offset = flo_curves::bezier::offset_lms_sampling(
             bezier,
@@ -201,7 +150,11 @@ pub fn variable_width_stroke<PD: PointData>(
             20,
             4.0,
         );
-        right_line.append_vec(right_offset.unwrap());
+        right_line.append_vec(
+            right_offset
+                .ok_or(MathError::OffsetFailed)
+                .map_err(MathError::in_segment(i))?,
+        );
     }
 
     right_line.beziers.reverse();
@@ -232,7 +185,7 @@ pub fn variable_width_stroke<PD: PointData>(
             out.push(right_pw);
         }
 
-        return Piecewise::new(out, None);
+        return Ok(Piecewise::new(out, None));
     } else {
         // path is not closed we need to cap the ends
         let mut out_builder = left_line;
@@ -248,11 +201,7 @@ pub fn variable_width_stroke<PD: PointData>(
         let tangent2 = -to.tangent_at(0.).normalize();
 
         let discontinuity_vec = to_start_point - from_end_point;
-        let dr = Vector {
-            x: discontinuity_vec.y,
-            y: -discontinuity_vec.x,
-        }
-        .normalize();
+        let dr = discontinuity_vec.right_normal();
 
         let tangent1 = if tangent1.dot(dr) < 0.9 { dr } else { tangent1 };
         let tangent2 = if tangent2.dot(dr) > -0.9 {
@@ -265,9 +214,13 @@ pub fn variable_width_stroke<PD: PointData>(
             CapType::Round => out_builder.arc_to(to.start_point(), tangent1, tangent2),
             CapType::Circle => out_builder.circle_arc_to(to.start_point(), tangent1, tangent2),
             CapType::Square => out_builder.line_to(to.start_point()),
-            CapType::Custom => {
-                out_builder.cap_to(to.start_point(), settings.cap_custom_end.as_ref().unwrap())
-            }
+            CapType::Custom => out_builder.cap_to(
+                to.start_point(),
+                settings
+                    .cap_custom_end
+                    .as_ref()
+                    .ok_or(MathError::MissingCap)?,
+            ),
         }
 
         // append the right line to the left now that we've connected them
@@ -285,11 +238,7 @@ pub fn variable_width_stroke<PD: PointData>(
         let tangent2 = -to.tangent_at(0.).normalize();
 
         let discontinuity_vec = to_start_point - from_end_point;
-        let dr = Vector {
-            x: discontinuity_vec.y,
-            y: -discontinuity_vec.x,
-        }
-        .normalize();
+        let dr = discontinuity_vec.right_normal();
 
         let tangent1 = if tangent1.dot(dr) < 0.9 { dr } else { tangent1 };
         let tangent2 = if tangent2.dot(dr) > -0.9 {
@@ -304,12 +253,15 @@ pub fn variable_width_stroke<PD: PointData>(
             CapType::Square => out_builder.line_to(to.start_point()),
             CapType::Custom => out_builder.cap_to(
                 to.start_point(),
-                settings.cap_custom_start.as_ref().unwrap(),
+                settings
+                    .cap_custom_start
+                    .as_ref()
+                    .ok_or(MathError::MissingCap)?,
             ),
         }
 
         let inner = Piecewise::new(out_builder.beziers, None);
-        return Piecewise::new(vec![inner], None);
+        return Ok(Piecewise::new(vec![inner], None));
     }
 }
 
@@ -317,24 +269,29 @@ pub fn variable_width_stroke_glif<PD: glifparser::PointData>(
     path: &Glif<PD>,
     settings: VWSSettings<PD>,
 ) -> Glif<PD> {
+    try_variable_width_stroke_glif(path, settings)
+        .unwrap_or_else(|err| panic!("Couldn't stroke the glyph: {}", err))
+}
+
+// Fallible version of variable_width_stroke_glif, errors from stroking a contour carry the index
+// of that contour.
+pub fn try_variable_width_stroke_glif<PD: glifparser::PointData>(
+    path: &Glif<PD>,
+    settings: VWSSettings<PD>,
+) -> MathResult<Glif<PD>> {
     // convert our path and pattern to piecewise collections of beziers
-    let piece_path = Piecewise::from(path.outline.as_ref().unwrap());
+    let piece_path = Piecewise::from(path.outline.as_ref().ok_or(MathError::MissingOutline)?);
     let mut output_outline: Outline<PD> = Vec::new();
 
-    let handles = parse_vws_lib(path);
-
-    if handles.is_none() {
-        panic!("No vws contours found in input!")
-    }
-
-    let handles = handles.expect("Input glyph has no lib node!");
+    let handles = try_parse_vws_lib(path)?;
 
     let iter = piece_path.segs.iter().enumerate();
     for (i, pwpath_contour) in iter {
         let vws_contour = &handles.get(i);
 
         if let Some(contour) = vws_contour {
-            let results = variable_width_stroke(&pwpath_contour, &contour, &settings);
+            let results = try_variable_width_stroke(&pwpath_contour, &contour, &settings)
+                .map_err(MathError::in_segment(i))?;
             for result_contour in results.segs {
                 output_outline.push(result_contour.to_contour());
             }
@@ -343,7 +300,7 @@ pub fn variable_width_stroke_glif<PD: glifparser::PointData>(
         }
     }
 
-    return Glif {
+    Ok(Glif {
         outline: Some(output_outline),
         anchors: path.anchors.clone(),
         width: path.width,
@@ -356,7 +313,7 @@ pub fn variable_width_stroke_glif<PD: glifparser::PointData>(
         note: path.note.clone(),
         filename: path.filename.clone(),
         ..Glif::default()
-    };
+    })
 }
 
 pub fn find_vws_contour(id: usize, vws_outline: &Vec<VWSContour>) -> Option<&VWSContour> {
@@ -364,132 +321,120 @@ pub fn find_vws_contour(id: usize, vws_outline: &Vec<VWSContour>) -> Option<&VWS
 }
 
 pub fn parse_vws_lib<T: glifparser::PointData>(input: &Glif<T>) -> Option<Vec<VWSContour>> {
+    try_parse_vws_lib(input).ok()
+}
+
+const VWS_LIB_KEY: &str = "io.MFEK.variable_width_stroke";
+
+// Fallible version of parse_vws_lib that says what's missing or malformed, errors in a contour's
+// data carry the index of that contour.
+pub fn try_parse_vws_lib<T: glifparser::PointData>(input: &Glif<T>) -> MathResult<Vec<VWSContour>> {
     let lib = if let GlifLib::Plist(ref lib) = input.lib {
         lib
     } else {
-        return None;
+        return Err(MathError::MissingLib(VWS_LIB_KEY));
     };
-    if let Some(lib) = lib.get("io.MFEK.variable_width_stroke") {
-        let mut vd: VecDeque<_> = lib.as_array().unwrap().clone().into();
-        let mut vws_outline = Vec::new();
-
-        while let Some(vws) = vd.pop_front() {
-            let vws = vws.as_dictionary().unwrap();
-
-            let _name = vws
-                .get("id")
-                .expect("VWSContour->id wrong type")
-                .as_string()
-                .expect("VWSContour must have a id");
-
-            let cap_start = vws
-                .get("cap_start")
-                .expect("VWSContour->cap_start wrong type")
-                .as_string()
-                .expect("VWSContour must have a cap_start");
-
-            let cap_end = vws
-                .get("cap_end")
-                .expect("VWSContour->cap_end wrong type")
-                .as_string()
-                .expect("VWSContour must have a cap_end");
-
-            let join = vws
-                .get("join")
-                .expect("VWSContour->join wrong type")
-                .as_string()
-                .expect("VWSContour must have a join");
-
-            let cap_start_type = match cap_start {
-                "round" => CapType::Round,
-                "circle" => CapType::Circle,
-                "square" => CapType::Square,
-                "custom" => CapType::Custom,
-                _ => panic!("Invalid start cap type!"),
-            };
-
-            let cap_end_type = match cap_end {
-                "round" => CapType::Round,
-                "circle" => CapType::Circle,
-                "square" => CapType::Square,
-                "custom" => CapType::Custom,
-                _ => panic!("Invalid end cap type!"),
-            };
-
-            let join_type = match join {
-                "round" => JoinType::Round,
-                "circle" => JoinType::Circle,
-                "miter" => JoinType::Miter,
-                "bevel" => JoinType::Bevel,
-                _ => panic!("Invalid join type!"),
-            };
-
-            let mut vws_handles = VWSContour {
-                handles: Vec::new(),
-                cap_start_type,
-                cap_end_type,
-                join_type,
-                remove_internal: false, // TODO: Add these to <lib>
-                remove_external: false,
-            };
-
-            let mut handles: VecDeque<_> = vws
-                .get("handles")
-                .expect("No handles in VWSContour?")
-                .as_array()
-                .expect("VWSContour->handles wrong type")
-                .clone()
-                .into();
-
-            while let Some(vws_handle) = handles.pop_front() {
-                let vws_handle = vws_handle.as_dictionary().expect("VWSHandle wrong type");
-
-                let left: f64 = vws_handle
-                    .get("left")
-                    .expect("VWSHandle->left wrong type")
-                    .as_real()
-                    .expect("VWSHandle must have a left");
-
-                let right: f64 = vws_handle
-                    .get("right")
-                    .expect("VWSHandle->right wrong type")
-                    .as_real()
-                    .expect("VWSHandle must have a right");
-
-                let tangent: f64 = vws_handle
-                    .get("tangent")
-                    .expect("VWSHandle->tangent wrong type")
-                    .as_real()
-                    .expect("VWSHandle must have a tangent");
-
-                let interpolation_string: &str = vws_handle
-                    .get("interpolation")
-                    .expect("VWSHandle->interpolation wrong type")
-                    .as_string()
-                    .expect("VWSHandle must have a interpolation");
-
-                let interpolation = match interpolation_string {
-                    "linear" => InterpolationType::Linear,
-                    _ => InterpolationType::Null,
-                };
-
-                vws_handles.handles.push(VWSHandle {
-                    left_offset: left,
-                    right_offset: right,
-                    tangent_offset: tangent,
-                    interpolation,
-                });
-            }
 
-            vws_outline.push(vws_handles);
-        }
+    let vws_lib = lib
+        .get(VWS_LIB_KEY)
+        .ok_or(MathError::MissingLib(VWS_LIB_KEY))?
+        .as_array()
+        .ok_or_else(|| malformed("the variable width stroke data must be an array"))?;
 
-        if vws_outline.len() > 0 {
-            return Some(vws_outline);
-        }
+    let mut vws_outline = Vec::new();
+    for (i, vws) in vws_lib.iter().enumerate() {
+        vws_outline.push(parse_vws_contour(vws).map_err(MathError::in_segment(i))?);
+    }
+
+    if vws_outline.is_empty() {
+        return Err(MathError::MissingLib(VWS_LIB_KEY));
+    }
+
+    Ok(vws_outline)
+}
+
+fn malformed(reason: &str) -> MathError {
+    MathError::MalformedLib(reason.to_string())
+}
+
+fn lib_string<'a>(dict: &'a plist::Dictionary, key: &str, node: &str) -> MathResult<&'a str> {
+    dict.get(key)
+        .and_then(|value| value.as_string())
+        .ok_or_else(|| malformed(&format!("{} must have a {} string", node, key)))
+}
+
+// generate_vws_lib writes the numbers out as strings so we accept those too
+fn lib_real(dict: &plist::Dictionary, key: &str, node: &str) -> MathResult<f64> {
+    let value = dict.get(key);
+
+    value
+        .and_then(|value| value.as_real())
+        .or_else(|| value.and_then(|value| value.as_string()?.parse().ok()))
+        .ok_or_else(|| malformed(&format!("{} must have a {} number", node, key)))
+}
+
+fn parse_vws_contour(vws: &plist::Value) -> MathResult<VWSContour> {
+    let vws = vws
+        .as_dictionary()
+        .ok_or_else(|| malformed("VWSContour must be a dictionary"))?;
+
+    let cap_start_type = match lib_string(vws, "cap_start", "VWSContour")? {
+        "round" => CapType::Round,
+        "circle" => CapType::Circle,
+        "square" => CapType::Square,
+        "custom" => CapType::Custom,
+        other => return Err(malformed(&format!("invalid start cap type {}", other))),
+    };
+
+    let cap_end_type = match lib_string(vws, "cap_end", "VWSContour")? {
+        "round" => CapType::Round,
+        "circle" => CapType::Circle,
+        "square" => CapType::Square,
+        "custom" => CapType::Custom,
+        other => return Err(malformed(&format!("invalid end cap type {}", other))),
+    };
+
+    let join_type = match lib_string(vws, "join", "VWSContour")? {
+        "round" => JoinType::Round,
+        "circle" => JoinType::Circle,
+        "miter" => JoinType::Miter,
+        "bevel" => JoinType::Bevel,
+        other => return Err(malformed(&format!("invalid join type {}", other))),
+    };
+
+    let mut vws_handles = VWSContour {
+        handles: Vec::new(),
+        cap_start_type,
+        cap_end_type,
+        join_type,
+        remove_internal: false, // TODO: Add these to <lib>
+        remove_external: false,
+    };
+
+    let handles = vws
+        .get("handles")
+        .and_then(|handles| handles.as_array())
+        .ok_or_else(|| malformed("VWSContour must have a handles array"))?;
+
+    for vws_handle in handles {
+        let vws_handle = vws_handle
+            .as_dictionary()
+            .ok_or_else(|| malformed("VWSHandle must be a dictionary"))?;
+
+        let interpolation = match lib_string(vws_handle, "interpolation", "VWSHandle")? {
+            "linear" => InterpolationType::Linear,
+            _ => InterpolationType::Null,
+        };
+
+        vws_handles.handles.push(VWSHandle {
+            left_offset: lib_real(vws_handle, "left", "VWSHandle")?,
+            right_offset: lib_real(vws_handle, "right", "VWSHandle")?,
+            tangent_offset: lib_real(vws_handle, "tangent", "VWSHandle")?,
+            interpolation,
+        });
     }
 
-    return None;
+    Ok(vws_handles)
 }
 
 fn generate_vws_lib_impl(vwscontours: &Vec<VWSContour>, applied: bool) -> GlifLib {
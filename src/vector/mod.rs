@@ -94,6 +94,15 @@ impl Vector {
     pub fn cross(self, v1: Vector) -> f64 {
         self.x * v1.y - self.y * v1.x
     }
+
+    // Unit vector perpendicular to this one, pointing to the right of it when it's a direction of travel.
+    pub fn right_normal(self) -> Vector {
+        Vector {
+            x: self.y,
+            y: -self.x,
+        }
+        .normalize()
+    }
 }
 
 impl Coordinate for Vector {
@@ -0,0 +1,236 @@
+use crate::quadbezier::QuadBezier;
+use crate::segment::Segment;
+use crate::subdivide::Subdivide;
+use crate::{Bezier, Evaluate, Piecewise, Vector};
+
+// Bisection steps used to find where a monotonic piece crosses a horizontal line, enough to get
+// down to the last bit of a t value.
+const BISECTION_STEPS: usize = 52;
+
+/// How the winding number of a point decides whether it's inside a shape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// Inside wherever the outline winds around the point at all, what fonts use.
+    NonZero,
+    /// Inside wherever the outline winds around the point an odd number of times.
+    EvenOdd,
+}
+
+impl FillRule {
+    pub fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// Winding numbers of points with respect to closed paths. Contours that don't end where they
+/// start are closed with a straight line like they would be when filled. Counter clockwise
+/// contours count positive. Points right on the outline can come out either way.
+pub trait Winding {
+    fn winding_number(&self, point: Vector) -> i32;
+
+    fn contains(&self, point: Vector, rule: FillRule) -> bool {
+        rule.is_inside(self.winding_number(point))
+    }
+}
+
+// A piece of a segment that only ever goes up or only ever goes down.
+#[derive(Clone, Debug)]
+struct MonotonicPiece {
+    bez: Bezier,
+    // +1 if the piece goes up, -1 if it goes down
+    direction: i32,
+    y_min: f64,
+    y_max: f64,
+    // bounds of the control points, which contain the piece
+    x_min: f64,
+    x_max: f64,
+}
+
+impl MonotonicPiece {
+    fn new(bez: Bezier) -> Option<Self> {
+        let (start, end) = (bez.w1.y, bez.w4.y);
+        // flat pieces never cross a horizontal ray
+        if start == end {
+            return None;
+        }
+
+        let xs = [bez.w1.x, bez.w2.x, bez.w3.x, bez.w4.x];
+        Some(MonotonicPiece {
+            direction: if end > start { 1 } else { -1 },
+            y_min: f64::min(start, end),
+            y_max: f64::max(start, end),
+            x_min: xs.iter().cloned().fold(f64::INFINITY, f64::min),
+            x_max: xs.iter().cloned().fold(-f64::INFINITY, f64::max),
+            bez,
+        })
+    }
+
+    // How this piece contributes to the winding number of point, by crossing the ray from point
+    // towards positive x. The pieces' y ranges are half open so a ray through the point where two
+    // pieces meet is only counted once.
+    fn winding_contribution(&self, point: Vector) -> i32 {
+        if point.y < self.y_min || point.y >= self.y_max || point.x >= self.x_max {
+            return 0;
+        }
+        if point.x < self.x_min {
+            return self.direction;
+        }
+
+        // the piece is monotonic so there's exactly one t where it's at the point's height
+        let (mut low, mut high) = if self.direction > 0 {
+            (0., 1.)
+        } else {
+            (1., 0.)
+        };
+        for _ in 0..BISECTION_STEPS {
+            let middle = (low + high) / 2.;
+            if self.bez.at(middle).y < point.y {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        if self.bez.at((low + high) / 2.).x > point.x {
+            self.direction
+        } else {
+            0
+        }
+    }
+}
+
+/// An outline cut up into pieces that are monotonic in y, so that testing a point against it only
+/// takes finding one crossing per piece. Build this once to test a lot of points against the same
+/// outline.
+#[derive(Clone, Debug)]
+pub struct MonotonicOutline {
+    pieces: Vec<MonotonicPiece>,
+}
+
+impl MonotonicOutline {
+    /// Takes the contours of an outline as runs of cubics.
+    pub fn new<'a, I>(contours: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Piecewise<Bezier>>,
+    {
+        let mut pieces = Vec::new();
+
+        for contour in contours {
+            let (start, end) = match (contour.segs.first(), contour.segs.last()) {
+                (Some(first), Some(last)) => (first.w1, last.w4),
+                _ => continue,
+            };
+
+            for bez in &contour.segs {
+                let (_, y_extrema) = bez.extrema();
+                pieces.extend(
+                    bez.split_at_multiple_t(y_extrema)
+                        .into_iter()
+                        .filter_map(MonotonicPiece::new),
+                );
+            }
+
+            if start != end {
+                pieces.extend(MonotonicPiece::new(Bezier::from_points(
+                    end, end, start, start,
+                )));
+            }
+        }
+
+        MonotonicOutline { pieces }
+    }
+
+    pub fn contains_points(&self, points: &[Vector], rule: FillRule) -> Vec<bool> {
+        points
+            .iter()
+            .map(|point| self.contains(*point, rule))
+            .collect()
+    }
+}
+
+impl Winding for MonotonicOutline {
+    fn winding_number(&self, point: Vector) -> i32 {
+        self.pieces
+            .iter()
+            .map(|piece| piece.winding_contribution(point))
+            .sum()
+    }
+}
+
+impl From<&Piecewise<Piecewise<Bezier>>> for MonotonicOutline {
+    fn from(outline: &Piecewise<Piecewise<Bezier>>) -> Self {
+        MonotonicOutline::new(&outline.segs)
+    }
+}
+
+// Quads and lines raise to cubics exactly, so everything else goes through a cubic copy.
+impl From<&Piecewise<Piecewise<QuadBezier>>> for MonotonicOutline {
+    fn from(outline: &Piecewise<Piecewise<QuadBezier>>) -> Self {
+        let contours: Vec<Piecewise<Bezier>> = outline
+            .segs
+            .iter()
+            .map(|contour| contour_to_cubic(contour, QuadBezier::to_cubic))
+            .collect();
+
+        MonotonicOutline::new(&contours)
+    }
+}
+
+impl From<&Piecewise<Piecewise<Segment>>> for MonotonicOutline {
+    fn from(outline: &Piecewise<Piecewise<Segment>>) -> Self {
+        MonotonicOutline::new(&outline.to_cubic().segs)
+    }
+}
+
+fn contour_to_cubic<T: Evaluate>(
+    contour: &Piecewise<T>,
+    to_cubic: impl Fn(&T) -> Bezier,
+) -> Piecewise<Bezier> {
+    Piecewise::new(contour.segs.iter().map(to_cubic).collect(), None)
+}
+
+impl Winding for Piecewise<Bezier> {
+    fn winding_number(&self, point: Vector) -> i32 {
+        MonotonicOutline::new(std::slice::from_ref(self)).winding_number(point)
+    }
+}
+
+impl Winding for Piecewise<QuadBezier> {
+    fn winding_number(&self, point: Vector) -> i32 {
+        contour_to_cubic(self, QuadBezier::to_cubic).winding_number(point)
+    }
+}
+
+impl Winding for Piecewise<Segment> {
+    fn winding_number(&self, point: Vector) -> i32 {
+        self.to_cubic().winding_number(point)
+    }
+}
+
+impl Winding for Piecewise<Piecewise<Bezier>> {
+    fn winding_number(&self, point: Vector) -> i32 {
+        MonotonicOutline::from(self).winding_number(point)
+    }
+}
+
+impl Winding for Piecewise<Piecewise<QuadBezier>> {
+    fn winding_number(&self, point: Vector) -> i32 {
+        MonotonicOutline::from(self).winding_number(point)
+    }
+}
+
+impl Winding for Piecewise<Piecewise<Segment>> {
+    fn winding_number(&self, point: Vector) -> i32 {
+        MonotonicOutline::from(self).winding_number(point)
+    }
+}
+
+impl Piecewise<Piecewise<Bezier>> {
+    /// Tests all of the points against the outline, decomposing it only once.
+    pub fn contains_points(&self, points: &[Vector], rule: FillRule) -> Vec<bool> {
+        MonotonicOutline::from(self).contains_points(points, rule)
+    }
+}
@@ -0,0 +1,59 @@
+use std::f64::consts;
+
+use MFEKmath::{Affine, Bezier, EvalRotate, EvalTransform, Evaluate, Piecewise, Vector};
+
+mod common;
+
+use common::v;
+
+fn close(a: Vector, b: Vector) -> bool {
+    (a - b).magnitude() < 1e-9
+}
+
+#[test]
+fn compose_and_invert() {
+    let rotate = Affine::rotate_about(consts::FRAC_PI_2, v(10., 10.));
+    assert!(close(rotate.apply(v(20., 10.)), v(10., 20.)));
+
+    let skew = Affine::skew(v(0.5, 0.));
+    assert!(close(skew.apply(v(0., 10.)), v(5., 10.)));
+
+    let reflect = Affine::reflect(v(0., 5.), v(1., 1.));
+    assert!(close(reflect.apply(v(0., 0.)), v(-5., 5.)));
+    assert!(reflect.determinant() < 0.);
+
+    let chain = Affine::translate(v(3., -4.))
+        .then(Affine::scale(v(2., 0.5)))
+        .then(rotate);
+    let p = v(7., 11.);
+    assert!(close(
+        chain.apply(p),
+        rotate.apply(Affine::scale(v(2., 0.5)).apply(p + v(3., -4.)))
+    ));
+    assert_eq!(
+        chain,
+        rotate * Affine::scale(v(2., 0.5)) * Affine::translate(v(3., -4.))
+    );
+
+    let inverse = chain.inverse().unwrap();
+    assert!(close(inverse.apply(chain.apply(p)), p));
+    assert!(close((chain * inverse).apply(p), p));
+
+    assert!(Affine::scale(v(1., 0.)).inverse().is_none());
+}
+
+#[test]
+fn transform_evaluate_types() {
+    let bez = Bezier::from_points(v(0., 0.), v(10., 20.), v(30., 20.), v(40., 0.));
+    let path = Piecewise::new(vec![bez.clone(), bez.reverse()], None);
+
+    let affine = Affine::reflect(v(0., 0.), v(0., 1.)).then(Affine::translate(v(5., 5.)));
+    let transformed = path.transform(&affine);
+    for i in 0..=10 {
+        let t = i as f64 / 10.;
+        assert!(close(transformed.at(t), affine.apply(path.at(t))));
+    }
+
+    let rotated = bez.transform(&Affine::rotate(1.));
+    assert!(close(rotated.at(0.3), bez.rotate(1.).at(0.3)));
+}
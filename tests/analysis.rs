@@ -0,0 +1,53 @@
+use MFEKmath::bezier::CubicType;
+use MFEKmath::{Bezier, Evaluate, Piecewise};
+
+mod common;
+
+use common::v;
+
+#[test]
+fn classify_cubics() {
+    let arch = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 100.), v(100., 0.));
+    assert_eq!(arch.classify(), CubicType::Arch);
+    assert!(arch.inflections().is_empty());
+
+    let serpentine = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 0.), v(100., 100.));
+    assert_eq!(serpentine.classify(), CubicType::Serpentine);
+    let inflections = serpentine.inflections();
+    assert_eq!(inflections.len(), 1);
+    assert!((inflections[0] - 0.5).abs() < 1e-9);
+
+    let cusp = Bezier::from_points(v(0., 0.), v(100., 100.), v(0., 100.), v(100., 0.));
+    assert_eq!(cusp.classify(), CubicType::Cusp);
+    assert!((cusp.cusp().unwrap() - 0.5).abs() < 1e-9);
+
+    let looped = Bezier::from_points(v(0., 0.), v(150., 100.), v(-50., 100.), v(100., 0.));
+    assert_eq!(looped.classify(), CubicType::Loop);
+    let (t1, t2) = looped.self_intersection().unwrap();
+    assert!(t1 < t2);
+    assert!(looped.at(t1).distance(looped.at(t2)) < 1e-9);
+
+    let line = Bezier::from_points(v(0., 0.), v(10., 10.), v(20., 20.), v(30., 30.));
+    assert_eq!(line.classify(), CubicType::Line);
+}
+
+#[test]
+fn split_at_critical_points() {
+    let serpentine = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 0.), v(100., 100.));
+    let looped = Bezier::from_points(v(0., 0.), v(150., 100.), v(-50., 100.), v(100., 0.));
+    let path = Piecewise::new(vec![serpentine, looped], None);
+
+    let split = path.split_at_critical_points();
+    assert_eq!(split.segs.len(), 2 + 3 + 1);
+    assert_eq!(split.cuts.len(), split.segs.len() + 1);
+
+    for seg in &split.segs {
+        let class = seg.classify();
+        assert!(class == CubicType::Arch || class == CubicType::Line);
+    }
+
+    for i in 0..=10 {
+        let t = i as f64 / 10.;
+        assert!(split.at(t).distance(path.at(t)) < 1e-6);
+    }
+}
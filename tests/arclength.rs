@@ -0,0 +1,158 @@
+use MFEKmath::piecewise::SegmentIterator;
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::{
+    AdaptiveArcLengthParameterization, ArcLength, ArcLengthParameterization, Bezier, Evaluate,
+    Line, Parameterization, Piecewise,
+};
+
+mod common;
+
+use common::v;
+
+// The length of the curve measured with a lot of tiny chords.
+fn chords<T: Evaluate>(curve: &T, t0: f64, t1: f64) -> f64 {
+    let steps = 100_000;
+    (0..steps)
+        .map(|i| {
+            let a = t0 + (t1 - t0) * i as f64 / steps as f64;
+            let b = t0 + (t1 - t0) * (i + 1) as f64 / steps as f64;
+            curve.at(a).distance(curve.at(b))
+        })
+        .sum()
+}
+
+#[test]
+fn lengths() {
+    let line = Line::from_points(v(0., 0.), v(30., 40.));
+    assert_eq!(line.arclen(1e-9), 50.);
+    assert_eq!(line.arclen_between(0.5, 1., 1e-9), 25.);
+
+    let straight = Bezier::from_points(v(0., 0.), v(10., 0.), v(20., 0.), v(30., 0.));
+    assert!((straight.arclen(1e-9) - 30.).abs() < 1e-9);
+
+    // a tight little curve and a long flat one
+    let tight = Bezier::from_points(v(0., 0.), v(5., 4.), v(-1., 5.), v(1., 0.));
+    let long = Bezier::from_points(v(0., 0.), v(300., 10.), v(700., -10.), v(1000., 0.));
+    for bez in &[tight, long] {
+        assert!((bez.arclen(1e-6) - chords(bez, 0., 1.)).abs() < 1e-4);
+        assert!((bez.arclen_between(0.2, 0.7, 1e-6) - chords(bez, 0.2, 0.7)).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn quadratics_have_a_closed_form() {
+    // y = x² from 0 to 1
+    let parabola = QuadBezier::from_points(v(0., 0.), v(0.5, 0.), v(1., 1.));
+    let exact = (2. * 5f64.sqrt() + 2f64.asinh()) / 4.;
+    assert!((parabola.arclen(1.) - exact).abs() < 1e-12);
+    assert!((parabola.arclen_between(0.3, 0.9, 1.) - chords(&parabola, 0.3, 0.9)).abs() < 1e-8);
+
+    // a straight quadratic doubling back on itself
+    let back = QuadBezier::from_points(v(0., 0.), v(20., 0.), v(0., 0.));
+    assert!((back.arclen(1e-9) - 20.).abs() < 1e-6);
+}
+
+#[test]
+fn lengths_back_to_t() {
+    let bez = Bezier::from_points(v(0., 0.), v(5., 40.), v(90., 40.), v(100., 0.));
+    let total = bez.arclen(1e-9);
+
+    for i in 0..=10 {
+        let length = total * i as f64 / 10.;
+        let t = bez.t_at_arclen(length, 1e-6);
+        assert!((bez.arclen_between(0., t, 1e-9) - length).abs() < 1e-6);
+    }
+    assert_eq!(bez.t_at_arclen(-1., 1e-6), 0.);
+    assert_eq!(bez.t_at_arclen(total + 1., 1e-6), 1.);
+}
+
+#[test]
+fn piecewise_lengths() {
+    let a = Bezier::from_points(v(0., 0.), v(0., 0.), v(10., 0.), v(10., 0.));
+    let b = Bezier::from_points(v(10., 0.), v(10., 30.), v(40., 30.), v(40., 0.));
+    let path = Piecewise::new(vec![a.clone(), b.clone()], None);
+
+    let total = path.arclen(1e-9);
+    assert!((total - (a.arclen(1e-9) + b.arclen(1e-9))).abs() < 1e-8);
+    assert!((path.arclen_between(0.25, 0.75, 1e-9) - chords(&path, 0.25, 0.75)).abs() < 1e-4);
+
+    // 5 along is halfway through the first segment, which is half of the piecewise's t
+    assert!((path.t_at_arclen(5., 1e-9) - 0.25).abs() < 1e-6);
+    let t = path.t_at_arclen(total - 1., 1e-9);
+    assert!(t > 0.5 && (path.arclen_between(t, 1., 1e-9) - 1.).abs() < 1e-6);
+
+    // speeds are in terms of the piecewise's t
+    assert!((path.speed_at(0.25) - 2. * a.speed_at(0.5)).abs() < 1e-9);
+}
+
+#[test]
+fn parameterization_tables() {
+    let a = Bezier::from_points(v(0., 0.), v(0., 0.), v(10., 0.), v(10., 0.));
+    let b = Bezier::from_points(v(10., 0.), v(10., 60.), v(50., 60.), v(50., 0.));
+    let c = Bezier::from_points(v(50., 0.), v(52., 5.), v(55., 5.), v(57., 0.));
+    let path = Piecewise::new(vec![a, b, c], None);
+    let total = path.arclen(1e-9);
+
+    let table = AdaptiveArcLengthParameterization::from_arclength(&path, 0.01);
+    assert!((table.get_total_arclen() - total).abs() < 0.01);
+    for i in 0..=20 {
+        let u = i as f64 / 20.;
+        let t = table.parameterize(u);
+        assert!((path.arclen_between(0., t, 1e-9) - u * total).abs() < 0.01);
+        assert!((table.get_arclen_from_t(t) - u * total).abs() < 0.01);
+    }
+
+    // the last interval of a table measured with chords counts up to the total
+    let chorded = ArcLengthParameterization::from(&path, 100);
+    let before_end = chorded.get_arclen_from_t(0.995);
+    assert!(before_end > chorded.arclens[99] && before_end < chorded.arclens[100]);
+    assert_eq!(chorded.get_arclen_from_t(1.), chorded.get_total_arclen());
+}
+
+#[test]
+fn arclength_cuts() {
+    // a short serif, a long bowl, and a segment with no length at all
+    let serif = Bezier::from_points(v(0., 0.), v(0., 0.), v(10., 0.), v(10., 0.));
+    let bowl = Bezier::from_points(v(10., 0.), v(10., 120.), v(190., 120.), v(190., 0.));
+    let nothing = Bezier::from_points(v(190., 0.), v(190., 0.), v(190., 0.), v(190., 0.));
+    let path = Piecewise::new(vec![serif.clone(), bowl.clone(), nothing], None);
+    let total = path.arclen(1e-9);
+
+    let by_length = path.with_arclength_cuts(1e-9);
+    assert_eq!(by_length.cuts.len(), 4);
+    assert!((by_length.cuts[1] - serif.arclen(1e-9) / total).abs() < 1e-9);
+    assert_eq!(by_length.cuts[2], 1.);
+    assert_eq!(by_length.cuts[3], 1.);
+    assert!((by_length.arclen(1e-9) - total).abs() < 1e-6);
+
+    // global t is proportional to length at every join
+    for &cut in &by_length.cuts {
+        assert!((by_length.arclen_between(0., cut, 1e-9) - cut * total).abs() < 1e-6);
+    }
+    let t = by_length.cuts[1] / 2.;
+    assert_eq!(by_length.seg_n(t), 0);
+    assert!((by_length.seg_t(t) - 0.5).abs() < 1e-12);
+    assert_eq!(by_length.at(t), serif.at(0.5));
+    assert_eq!(by_length.at(1.), v(190., 0.));
+
+    let ranges: Vec<_> = SegmentIterator::new(by_length.clone())
+        .map(|(_, start, end)| (start, end))
+        .collect();
+    assert_eq!(ranges[0], (0., by_length.cuts[1]));
+    assert_eq!(ranges[1], (by_length.cuts[1], 1.));
+    assert_eq!(ranges[2], (1., 1.));
+
+    // the segment with no width has no speed rather than an undefined one
+    assert_eq!(by_length.seg_n(1.), 2);
+    assert_eq!(by_length.speed_at(1.), 0.);
+    assert!(by_length.speed_at(0.5).is_finite());
+    let table = AdaptiveArcLengthParameterization::from_arclength(&by_length, 0.001);
+    assert!((table.get_total_arclen() - total).abs() < 0.001);
+
+    // the same cuts from a parameterization
+    let table = AdaptiveArcLengthParameterization::from_arclength(&path, 0.001);
+    let from_table = path.with_cuts_from(&table);
+    for (a, b) in from_table.cuts.iter().zip(&by_length.cuts) {
+        assert!((a - b).abs() * total < 0.001);
+    }
+}
@@ -0,0 +1,101 @@
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::{Area, Bezier, Evaluate, Orientation, Piecewise, Reverse, Vector};
+
+mod common;
+
+use common::{rectangle, v};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9 * b.abs().max(1.)
+}
+
+// the shoelace area of the curve flattened into a lot of lines
+fn flattened_area(bez: &Bezier, steps: usize) -> f64 {
+    let points: Vec<Vector> = (0..=steps)
+        .map(|i| bez.at(i as f64 / steps as f64))
+        .collect();
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<f64>()
+        / 2.
+}
+
+#[test]
+fn area_and_orientation() {
+    let rect = rectangle(v(10., 20.), 30., 40.);
+    assert!(close(rect.signed_area(), 1200.));
+    assert_eq!(rect.orientation(), Some(Orientation::CounterClockwise));
+    assert!(close(rect.reverse().signed_area(), -1200.));
+    assert_eq!(rect.reverse().orientation(), Some(Orientation::Clockwise));
+
+    // the curve is closed by the line from its end back to its start
+    let bez = Bezier::from_points(v(0., 0.), v(-10., 80.), v(120., -40.), v(60., 30.));
+    let curve = Piecewise::new(vec![bez.clone()], None);
+    assert!((curve.signed_area() - flattened_area(&bez, 20000)).abs() < 1e-3);
+    assert!(curve.approximate_area() == 0.);
+
+    // a parabola cut by its chord has 2/3 the area of the triangle of its control points
+    let quad = Piecewise::new(
+        vec![QuadBezier::from_points(
+            v(0., 0.),
+            v(50., -60.),
+            v(100., 0.),
+        )],
+        None,
+    );
+    assert!(close(quad.signed_area(), 2. / 3. * 3000.));
+
+    let outline = Piecewise::new(
+        vec![
+            rectangle(v(0., 0.), 100., 100.),
+            rectangle(v(25., 25.), 50., 50.).reverse(),
+        ],
+        None,
+    );
+    assert!(close(outline.signed_area(), 7500.));
+}
+
+#[test]
+fn moments() {
+    let rect = rectangle(v(10., 20.), 30., 40.);
+    let moments = rect.moments();
+    let centroid = moments.centroid().unwrap();
+    assert!(close(centroid.x, 25.) && close(centroid.y, 40.));
+
+    let (xx, xy, yy) = moments.central_second_moments().unwrap();
+    assert!(close(xx, 40. * 30f64.powi(3) / 12.));
+    assert!(close(yy, 30. * 40f64.powi(3) / 12.));
+    assert!(xy.abs() < 1e-6);
+
+    // a symmetric curved shape has its centroid on the axis of symmetry
+    let lens = Piecewise::new(
+        vec![
+            Bezier::from_points(v(0., 0.), v(30., -40.), v(70., -40.), v(100., 0.)),
+            Bezier::from_points(v(100., 0.), v(70., 10.), v(30., 10.), v(0., 0.)),
+        ],
+        None,
+    );
+    assert!(close(lens.centroid().unwrap().x, 50.));
+
+    // the centroid doesn't care which way the contour runs
+    let reversed = lens.reverse().centroid().unwrap();
+    assert!((reversed - lens.centroid().unwrap()).magnitude() < 1e-9);
+}
+
+#[test]
+fn reverse_keeps_cuts() {
+    let bez = Bezier::from_points(v(0., 0.), v(10., 20.), v(30., 20.), v(40., 0.));
+    let path = Piecewise::new(
+        vec![bez.clone(), bez.reverse(), bez.clone()],
+        Some(vec![0., 0.2, 0.3, 1.]),
+    );
+    let reversed = path.reverse();
+
+    assert_eq!(reversed.cuts, vec![0., 0.7, 0.8, 1.]);
+    for i in 0..=20 {
+        let t = i as f64 / 20.;
+        assert!((reversed.at(t) - path.at(1. - t)).magnitude() < 1e-9);
+    }
+}
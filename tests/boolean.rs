@@ -0,0 +1,250 @@
+use glifparser::{Glif, Handle, PointType};
+use MFEKmath::boolean::remove_overlaps_glif;
+use MFEKmath::subdivide::Subdivide;
+use MFEKmath::{
+    Area, Bezier, BooleanOp, Evaluate, FillRule, Orientation, Piecewise, Reverse, Segment, Winding,
+};
+
+mod common;
+
+use common::{circle, outline, rectangle, v};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6 * b.abs().max(1.)
+}
+
+#[test]
+fn overlapping_squares() {
+    let a = outline(vec![rectangle(v(0., 0.), 100., 100.)]);
+    let b = outline(vec![rectangle(v(50., 50.), 100., 100.)]);
+
+    let union = a.union(&b);
+    assert_eq!(union.segs.len(), 1);
+    assert!(close(union.signed_area(), 17500.));
+    assert!(close(a.intersection(&b).signed_area(), 2500.));
+    assert!(close(a.difference(&b).signed_area(), 7500.));
+    assert!(close(b.difference(&a).signed_area(), 7500.));
+    assert_eq!(a.xor(&b).segs.len(), 2);
+    assert!(close(a.xor(&b).signed_area(), 15000.));
+
+    // the operands' direction doesn't matter, only what they fill
+    let reversed = outline(vec![rectangle(v(50., 50.), 100., 100.).reverse()]);
+    assert!(close(a.union(&reversed).signed_area(), 17500.));
+}
+
+#[test]
+fn curves_stay_curves() {
+    let a = outline(vec![circle(v(0., 0.), 100.)]);
+    let b = outline(vec![circle(v(120., 0.), 100.)]);
+
+    let union = a.union(&b);
+    let intersection = a.intersection(&b);
+    assert_eq!(union.segs.len(), 1);
+    assert_eq!(intersection.segs.len(), 1);
+    assert!(close(
+        union.signed_area() + intersection.signed_area(),
+        a.signed_area() + b.signed_area()
+    ));
+    assert!(close(
+        a.xor(&b).signed_area(),
+        union.signed_area() - intersection.signed_area()
+    ));
+
+    // every edge of the result is a piece of one of the circles
+    for bez in &union.segs[0].segs {
+        for i in 0..=10 {
+            let point = bez.at(i as f64 / 10.);
+            let radius = f64::min(point.magnitude(), point.distance(v(120., 0.)));
+            assert!((radius - 100.).abs() < 0.03);
+        }
+    }
+
+    assert!(union.contains(v(-90., 0.), FillRule::NonZero));
+    assert!(union.contains(v(210., 0.), FillRule::NonZero));
+    assert!(!intersection.contains(v(10., 0.), FillRule::NonZero));
+    assert!(intersection.contains(v(60., 0.), FillRule::NonZero));
+}
+
+#[test]
+fn coincident_edges_and_shared_points() {
+    let a = outline(vec![rectangle(v(0., 0.), 100., 100.)]);
+    let beside = outline(vec![rectangle(v(100., 0.), 100., 100.)]);
+
+    let union = a.union(&beside);
+    assert_eq!(union.segs.len(), 1);
+    assert!(close(union.signed_area(), 20000.));
+    assert!(a.intersection(&beside).segs.is_empty());
+
+    let same = a.union(&a);
+    assert_eq!(same.segs.len(), 1);
+    assert!(close(same.signed_area(), 10000.));
+
+    // touching at a single corner gives two separate contours
+    let corner = outline(vec![rectangle(v(100., 100.), 100., 100.)]);
+    let union = a.union(&corner);
+    assert_eq!(union.segs.len(), 2);
+    assert!(close(union.signed_area(), 20000.));
+}
+
+#[test]
+fn holes() {
+    let a = outline(vec![rectangle(v(0., 0.), 100., 100.)]);
+    let b = outline(vec![circle(v(50., 50.), 25.)]);
+
+    let ring = a.difference(&b);
+    assert_eq!(ring.segs.len(), 2);
+    assert!(close(ring.signed_area(), a.signed_area() - b.signed_area()));
+
+    let orientations: Vec<_> = ring
+        .segs
+        .iter()
+        .map(|contour| contour.orientation())
+        .collect();
+    assert!(orientations.contains(&Some(Orientation::Clockwise)));
+    assert!(orientations.contains(&Some(Orientation::CounterClockwise)));
+
+    assert!(close(
+        a.boolean(&b, BooleanOp::Intersection).signed_area(),
+        b.signed_area()
+    ));
+    assert!(close(a.union(&b).signed_area(), a.signed_area()));
+}
+
+#[test]
+fn remove_overlaps() {
+    let square = rectangle(v(0., 0.), 100., 100.);
+    let untouched = outline(vec![square.clone()]).remove_overlaps();
+    assert_eq!(untouched.segs.len(), 1);
+    assert_eq!(untouched.segs[0].segs.len(), 4);
+
+    let overlapping = outline(vec![square.clone(), rectangle(v(50., 50.), 100., 100.)]);
+    let merged = overlapping.remove_overlaps();
+    assert_eq!(merged.segs.len(), 1);
+    assert_eq!(merged.segs[0].segs.len(), 8);
+    assert!(close(merged.signed_area(), 17500.));
+    // the corners away from the overlap are still on-curve points
+    for corner in &[v(0., 0.), v(100., 0.), v(150., 150.), v(0., 100.)] {
+        assert!(merged.segs[0].segs.iter().any(|bez| bez.w1 == *corner));
+    }
+
+    // clockwise outlines stay clockwise
+    let clockwise = outline(vec![
+        square.reverse(),
+        rectangle(v(50., 50.), 100., 100.).reverse(),
+    ]);
+    let merged = clockwise.remove_overlaps();
+    assert_eq!(merged.segs[0].orientation(), Some(Orientation::Clockwise));
+    assert!(close(merged.signed_area(), -17500.));
+
+    // a bow tie crossing itself in the middle comes apart into its two halves
+    let bow_tie = outline(vec![Piecewise::new(
+        vec![
+            Bezier::from_points(v(0., 0.), v(0., 0.), v(100., 100.), v(100., 100.)),
+            Bezier::from_points(v(100., 100.), v(100., 100.), v(100., 0.), v(100., 0.)),
+            Bezier::from_points(v(100., 0.), v(100., 0.), v(0., 100.), v(0., 100.)),
+            Bezier::from_points(v(0., 100.), v(0., 100.), v(0., 0.), v(0., 0.)),
+        ],
+        None,
+    )]);
+    let untangled = bow_tie.remove_overlaps();
+    assert_eq!(untangled.segs.len(), 2);
+    assert!(close(untangled.signed_area().abs(), 5000.));
+    for contour in &untangled.segs {
+        assert!(close(contour.signed_area().abs(), 2500.));
+    }
+}
+
+// A cubic that crosses itself, closed with a line. Returns it with the areas of its loop and of
+// the rest.
+fn looping_cubic() -> (Piecewise<Bezier>, f64, f64) {
+    let bez = Bezier::from_points(v(0., 0.), v(300., 200.), v(-200., 200.), v(100., 0.));
+    let closing = Bezier::from_points(v(100., 0.), v(100., 0.), v(0., 0.), v(0., 0.));
+    let (s, t) = bez.self_intersection().unwrap();
+
+    let lobe = Piecewise::new(vec![bez.subsegment(s, t)], None);
+    let rest = Piecewise::new(
+        vec![
+            bez.subsegment(0., s),
+            bez.subsegment(t, 1.),
+            closing.clone(),
+        ],
+        None,
+    );
+
+    (
+        Piecewise::new(vec![bez, closing], None),
+        lobe.signed_area().abs(),
+        rest.signed_area().abs(),
+    )
+}
+
+#[test]
+fn self_intersecting_cubic() {
+    let (contour, lobe, rest) = looping_cubic();
+    let looping = outline(vec![contour]);
+    let far = outline(vec![rectangle(v(500., 0.), 100., 100.)]);
+
+    // the loop and the rest only touch where the cubic crosses itself, so they come apart
+    let union = looping.union(&far);
+    assert_eq!(union.segs.len(), 3);
+    assert!(close(union.signed_area(), lobe + rest + 10000.));
+    for contour in &union.segs {
+        assert_eq!(contour.orientation(), Some(Orientation::CounterClockwise));
+    }
+}
+
+#[test]
+fn remove_overlaps_of_a_looping_cubic() {
+    let (contour, lobe, rest) = looping_cubic();
+    let untangled = outline(vec![contour]).remove_overlaps();
+
+    assert_eq!(untangled.segs.len(), 2);
+    assert!(close(untangled.signed_area().abs(), lobe + rest));
+    let orientations: Vec<_> = untangled
+        .segs
+        .iter()
+        .map(|contour| contour.orientation())
+        .collect();
+    assert_eq!(orientations[0], orientations[1]);
+}
+
+#[test]
+fn remove_overlaps_keeps_lines() {
+    let overlapping = outline(vec![
+        rectangle(v(0., 0.), 100., 100.),
+        rectangle(v(50., 50.), 100., 100.),
+    ]);
+    let merged = Piecewise::<Piecewise<Segment>>::from(&overlapping.remove_overlaps());
+    assert!(merged.segs[0]
+        .segs
+        .iter()
+        .all(|seg| matches!(seg, Segment::Line(_))));
+
+    let glif: Glif<()> = glifparser::read(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="squares" format="2">
+  <outline>
+    <contour>
+      <point x="0" y="0" type="line"/>
+      <point x="100" y="0" type="line"/>
+      <point x="100" y="100" type="line"/>
+      <point x="0" y="100" type="line"/>
+    </contour>
+    <contour>
+      <point x="50" y="50" type="line"/>
+      <point x="150" y="50" type="line"/>
+      <point x="150" y="150" type="line"/>
+      <point x="50" y="150" type="line"/>
+    </contour>
+  </outline>
+</glyph>"#,
+    )
+    .unwrap();
+    let merged = remove_overlaps_glif(&glif);
+    let contours = merged.outline.unwrap();
+    assert_eq!(contours.len(), 1);
+    assert_eq!(contours[0].len(), 8);
+    assert!(contours[0]
+        .iter()
+        .all(|point| point.ptype == PointType::Line && matches!(point.a, Handle::Colocated)));
+}
@@ -0,0 +1,58 @@
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::{Bezier, Evaluate, Piecewise, Vector};
+
+fn assert_near(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn cubic_bounds_are_tight() {
+    let bez = Bezier::from_points(
+        Vector::from_components(0., 0.),
+        Vector::from_components(0., 100.),
+        Vector::from_components(100., 100.),
+        Vector::from_components(100., 0.),
+    );
+
+    let (x_extrema, y_extrema) = bez.extrema();
+    assert!(x_extrema.is_empty());
+    assert_eq!(y_extrema.len(), 1);
+    assert_near(y_extrema[0], 0.5);
+
+    let bounds = bez.bounds();
+    assert_near(bounds.left, 0.);
+    assert_near(bounds.right, 100.);
+    assert_near(bounds.bottom, 0.);
+    assert_near(bounds.top, 75.);
+}
+
+#[test]
+fn quad_and_piecewise_bounds_are_tight() {
+    let quad = QuadBezier::from_points(
+        Vector::from_components(0., 0.),
+        Vector::from_components(50., 100.),
+        Vector::from_components(100., 0.),
+    );
+    assert_near(quad.bounds().top, 50.);
+
+    let pw = Piecewise::new(
+        vec![
+            Bezier::from_points(
+                Vector::from_components(0., 0.),
+                Vector::from_components(0., 100.),
+                Vector::from_components(100., 100.),
+                Vector::from_components(100., 0.),
+            ),
+            Bezier::from_points(
+                Vector::from_components(100., 0.),
+                Vector::from_components(100., -100.),
+                Vector::from_components(0., -100.),
+                Vector::from_components(0., 0.),
+            ),
+        ],
+        None,
+    );
+    let bounds = pw.bounds();
+    assert_near(bounds.top, 75.);
+    assert_near(bounds.bottom, -75.);
+}
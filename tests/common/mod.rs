@@ -0,0 +1,86 @@
+// Fixtures shared by the integration tests. Each test file only uses some of them.
+#![allow(dead_code)]
+
+use MFEKmath::{Bezier, Piecewise, Vector};
+
+pub fn v(x: f64, y: f64) -> Vector {
+    Vector::from_components(x, y)
+}
+
+// A straight cubic with its handles on its points, the way lines come out of a glyph.
+pub fn line(a: Vector, b: Vector) -> Bezier {
+    Bezier::from_points(a, a, b, b)
+}
+
+// A straight cubic with its handles a third of the way in from either end, so it moves at an even
+// speed.
+pub fn even_line(a: Vector, b: Vector) -> Bezier {
+    Bezier::from_points(a, a.lerp(b, 1. / 3.), a.lerp(b, 2. / 3.), b)
+}
+
+// A closed contour of lines through the points.
+pub fn polygon(points: &[Vector]) -> Piecewise<Bezier> {
+    let segs = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| line(*a, *b))
+        .collect();
+
+    Piecewise::new(segs, None)
+}
+
+// A counter clockwise rectangle with its bottom left corner at origin.
+pub fn rectangle(origin: Vector, width: f64, height: f64) -> Piecewise<Bezier> {
+    polygon(&[
+        origin,
+        origin + v(width, 0.),
+        origin + v(width, height),
+        origin + v(0., height),
+    ])
+}
+
+// A counter clockwise circle made of four cubics.
+pub fn circle(center: Vector, radius: f64) -> Piecewise<Bezier> {
+    let k = 0.5522847498 * radius;
+    let (r, c) = (radius, center);
+    Piecewise::new(
+        vec![
+            Bezier::from_points(c + v(r, 0.), c + v(r, k), c + v(k, r), c + v(0., r)),
+            Bezier::from_points(c + v(0., r), c + v(-k, r), c + v(-r, k), c + v(-r, 0.)),
+            Bezier::from_points(c + v(-r, 0.), c + v(-r, -k), c + v(-k, -r), c + v(0., -r)),
+            Bezier::from_points(c + v(0., -r), c + v(k, -r), c + v(r, -k), c + v(r, 0.)),
+        ],
+        None,
+    )
+}
+
+pub fn outline(contours: Vec<Piecewise<Bezier>>) -> Piecewise<Piecewise<Bezier>> {
+    Piecewise::new(contours, None)
+}
+
+// Lays the pattern along the path once, as it is and starting from its left edge.
+#[cfg(feature = "skia")]
+pub fn pattern_settings(reverse_path: bool) -> MFEKmath::PatternSettings {
+    use glifparser::glif::contour_operations::pap::{
+        PatternCopies, PatternCulling, PatternStretch, PatternSubdivide,
+    };
+
+    MFEKmath::PatternSettings {
+        copies: PatternCopies::Single,
+        subdivide: PatternSubdivide::Off,
+        is_vertical: false,
+        stretch: PatternStretch::Off,
+        spacing: 0.,
+        simplify: false,
+        normal_offset: 0.,
+        tangent_offset: 0.,
+        pattern_scale: v(1., 1.),
+        center_pattern: false,
+        cull_overlap: PatternCulling::Off,
+        two_pass_culling: false,
+        reverse_culling: false,
+        reverse_path,
+        warp_pattern: true,
+        split_path: false,
+    }
+}
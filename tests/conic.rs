@@ -0,0 +1,100 @@
+use MFEKmath::subdivide::Subdivide;
+use MFEKmath::{Conic, Evaluate, MathError};
+
+mod common;
+
+use common::v;
+
+fn quarter_circle() -> Conic {
+    Conic::from_points(v(100., 0.), v(100., 100.), v(0., 100.), 0.5f64.sqrt())
+}
+
+#[test]
+fn exact_circle() {
+    let arc = quarter_circle();
+
+    for i in 0..=10 {
+        let t = i as f64 / 10.;
+        assert!((arc.at(t).magnitude() - 100.).abs() < 1e-9);
+        assert!((arc.radius_of_curvature_at(t) - 100.).abs() < 1e-6);
+    }
+
+    let h = 1e-6;
+    for t in [0.2, 0.5, 0.9] {
+        let tangent = (arc.at(t + h) - arc.at(t - h)) / (2. * h);
+        assert!((arc.tangent_at(t) - tangent).magnitude() < 1e-4);
+
+        let second = (arc.tangent_at(t + h) - arc.tangent_at(t - h)) / (2. * h);
+        assert!((arc.second_derivative_at(t) - second).magnitude() < 1e-3);
+    }
+
+    // a 120 degree arc around the origin bulges out to x = 100 in the middle
+    let arc = Conic::from_points(
+        v(50., -75f64.sqrt() * 10.),
+        v(200., 0.),
+        v(50., 75f64.sqrt() * 10.),
+        0.5,
+    );
+    let bounds = arc.bounds();
+    assert!((bounds.right - 100.).abs() < 1e-9);
+    assert!((bounds.left - 50.).abs() < 1e-9);
+    assert!((bounds.top - 75f64.sqrt() * 10.).abs() < 1e-9);
+}
+
+#[test]
+fn subdivision() {
+    let arc = quarter_circle();
+
+    let (first, second) = arc.split(0.5).unwrap();
+    assert!((first.weight - ((1. + arc.weight) / 2.).sqrt()).abs() < 1e-12);
+    assert!((first.end_point() - arc.at(0.5)).magnitude() < 1e-9);
+    for i in 0..=10 {
+        let t = i as f64 / 10.;
+        assert!((first.at(t).magnitude() - 100.).abs() < 1e-9);
+        assert!((second.at(t).magnitude() - 100.).abs() < 1e-9);
+    }
+
+    let pieces = arc.split_at_multiple_t(vec![0.25, 0.6, 0.8]);
+    assert_eq!(pieces.len(), 4);
+    for (piece, t) in pieces.iter().zip([0.25, 0.6, 0.8, 1.]) {
+        assert!((piece.end_point() - arc.at(t)).magnitude() < 1e-9);
+    }
+}
+
+#[test]
+fn conic_to_cubics() {
+    let arc = quarter_circle().apply_transform(|p| *p * 5.);
+
+    let cubics = arc.to_cubics(0.001);
+    assert!(cubics.len() > 1);
+    assert_eq!(cubics.first().unwrap().start_point(), arc.start_point());
+    assert_eq!(cubics.last().unwrap().end_point(), arc.end_point());
+
+    for pair in cubics.windows(2) {
+        assert_eq!(pair[0].end_point(), pair[1].start_point());
+    }
+    for cubic in &cubics {
+        for i in 0..=20 {
+            let p = cubic.at(i as f64 / 20.);
+            assert!((p.magnitude() - 500.).abs() <= 0.001);
+        }
+    }
+
+    // an ordinary quadratic comes out as its exact cubic
+    let quad = Conic::from_points(v(0., 0.), v(50., 100.), v(100., 0.), 1.);
+    assert_eq!(quad.to_cubics(0.001).len(), 1);
+
+    assert_eq!(
+        arc.try_to_cubics(0.).unwrap_err(),
+        MathError::InvalidTolerance(0.)
+    );
+    assert_eq!(
+        arc.try_to_cubics(-1.).unwrap_err(),
+        MathError::InvalidTolerance(-1.)
+    );
+    let broken = Conic::from_points(v(0., 0.), v(50., f64::NAN), v(100., 0.), 1.);
+    assert_eq!(
+        broken.try_to_cubics(0.001).unwrap_err(),
+        MathError::NonFinite
+    );
+}
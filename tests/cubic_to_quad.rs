@@ -0,0 +1,140 @@
+use glifparser::glif::point::quad::QPoint;
+use MFEKmath::error::MathError;
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::{Bezier, Evaluate, NearestPoint, Piecewise};
+
+mod common;
+
+use common::v;
+
+fn max_deviation(bez: &Bezier, quads: &[QuadBezier]) -> f64 {
+    let mut max: f64 = 0.;
+    for quad in quads {
+        for i in 0..=20 {
+            let (_, _, distance) = bez.nearest_point(quad.at(i as f64 / 20.));
+            max = max.max(distance);
+        }
+    }
+    max
+}
+
+#[test]
+fn cubic_to_quads_within_tolerance() {
+    let arch = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 100.), v(100., 0.));
+
+    for tolerance in [1., 0.1, 0.01] {
+        let quads = QuadBezier::from_cubic(&arch, tolerance, 1);
+        assert!(max_deviation(&arch, &quads) <= tolerance);
+        assert!(quads[0].start_point().is_near(arch.w1, 1e-9));
+        assert!(quads.last().unwrap().end_point().is_near(arch.w4, 1e-9));
+        for pair in quads.windows(2) {
+            assert!(pair[0].end_point().is_near(pair[1].start_point(), 1e-9));
+        }
+    }
+
+    assert_eq!(QuadBezier::from_cubic(&arch, 1., 5).len(), 5);
+
+    // a cubic that is really a quadratic converts exactly
+    let quad = QuadBezier::from_points(v(0., 0.), v(50., 100.), v(100., 0.));
+    assert_eq!(QuadBezier::from_cubic(&quad.to_cubic(), 0.001, 1).len(), 1);
+}
+
+#[test]
+fn compatible_masters() {
+    let light = Piecewise::new(
+        vec![Bezier::from_points(
+            v(0., 0.),
+            v(0., 10.),
+            v(10., 10.),
+            v(10., 0.),
+        )],
+        None,
+    );
+    let bold = Piecewise::new(
+        vec![Bezier::from_points(
+            v(0., 0.),
+            v(0., 300.),
+            v(900., 300.),
+            v(900., 0.),
+        )],
+        None,
+    );
+
+    let converted =
+        Piecewise::<Bezier>::to_compatible_quadratics(&[light.clone(), bold], 0.5, 1).unwrap();
+    assert_eq!(converted[0].segs.len(), converted[1].segs.len());
+    assert!(converted[1].segs.len() > 1);
+    assert_eq!(converted[0].cuts, converted[1].cuts);
+
+    let longer = Piecewise::new(vec![light.segs[0].clone(), light.segs[0].clone()], None);
+    assert_eq!(
+        Piecewise::<Bezier>::to_compatible_quadratics(&[light, longer], 0.5, 1).err(),
+        Some(MathError::IncompatibleMasters)
+    );
+}
+
+#[test]
+fn bad_input_is_an_error() {
+    let arch = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 100.), v(100., 0.));
+    assert_eq!(
+        QuadBezier::try_from_cubic(&arch, 0., 1).err(),
+        Some(MathError::InvalidTolerance(0.))
+    );
+    assert!(matches!(
+        QuadBezier::try_from_cubic(&arch, f64::NAN, 1),
+        Err(MathError::InvalidTolerance(_))
+    ));
+
+    let broken = Bezier::from_points(v(0., 0.), v(f64::NAN, 100.), v(100., 100.), v(100., 0.));
+    assert_eq!(
+        QuadBezier::try_from_cubic(&broken, 1., 1).err(),
+        Some(MathError::NonFinite)
+    );
+    let huge = Bezier::from_points(
+        v(0., 0.),
+        v(f64::INFINITY, 100.),
+        v(100., 100.),
+        v(100., 0.),
+    );
+    assert_eq!(
+        QuadBezier::try_from_cubic(&huge, 1., 1).err(),
+        Some(MathError::NonFinite)
+    );
+
+    // the error says which segment couldn't be converted
+    let path = Piecewise::new(vec![arch.clone(), broken], None);
+    let err = path.try_to_quadratic(1., 1).unwrap_err();
+    assert_eq!(err.indices(), vec![1]);
+    assert_eq!(err.root_cause(), &MathError::NonFinite);
+
+    // a tolerance too tight for any reasonable number of quads
+    assert_eq!(
+        QuadBezier::try_from_cubic(&arch, 1e-300, 1).err(),
+        Some(MathError::NoFit)
+    );
+}
+
+#[test]
+fn quads_round_trip_through_quad_points() {
+    let arch = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 100.), v(100., 0.));
+    let base = Bezier::from_points(v(100., 0.), v(100., 0.), v(0., 0.), v(0., 0.));
+    let path = Piecewise::new(vec![arch, base], None);
+    let quads = path.to_quadratic(0.1, 1);
+    assert!(quads.is_closed());
+
+    // a closed contour has one point per quad, holding the quad's start and handle
+    let points: Vec<QPoint<()>> = quads.to_contour();
+    assert_eq!(points.len(), quads.segs.len());
+
+    for (i, quad) in quads.segs.iter().enumerate() {
+        let next = &points[(i + 1) % points.len()];
+        let read = QuadBezier::from(&points[i], next);
+        for (a, b) in read
+            .to_control_points()
+            .iter()
+            .zip(quad.to_control_points())
+        {
+            assert!(a.is_near(b, 1e-3));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use MFEKmath::consts::SMALL_T_DISTANCE;
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::{Bezier, Evaluate, Piecewise};
+
+mod common;
+
+use common::v;
+
+#[test]
+fn curvature_and_normals() {
+    // quarter circle of radius 100, turning left
+    let k = 0.5522847498;
+    let arc = Bezier::from_points(
+        v(100., 0.),
+        v(100., 100. * k),
+        v(100. * k, 100.),
+        v(0., 100.),
+    );
+
+    for t in [0., 0.25, 0.5, 1.] {
+        assert!((arc.radius_of_curvature_at(t) - 100.).abs() < 3.);
+        // the normal points to the right of travel, away from the center
+        assert!((arc.normal_at(t) - arc.at(t).normalize()).magnitude() < 0.01);
+    }
+
+    let line = Bezier::from_points(v(0., 0.), v(10., 0.), v(20., 0.), v(30., 0.));
+    assert_eq!(line.curvature_at(0.5), 0.);
+    assert_eq!(line.radius_of_curvature_at(0.5), f64::INFINITY);
+
+    let quad = QuadBezier::from_points(v(0., 0.), v(50., 100.), v(100., 0.));
+    assert!((quad.second_derivative_at(0.3) - v(0., -400.)).magnitude() < 1e-9);
+    assert!(quad.curvature_at(0.5) < 0.);
+}
+
+#[test]
+fn colocated_handles() {
+    let bez = Bezier::from_points(v(0., 0.), v(0., 0.), v(100., 100.), v(100., 0.));
+
+    let normal = bez.normal_at(0.);
+    assert!(normal.x.is_finite() && normal.y.is_finite());
+    assert!((normal - v(1., -1.).normalize()).magnitude() < 0.01);
+    assert!(bez.curvature_at(0.).is_finite());
+    // the curvature blows up towards an end like that, so it's taken just inside it
+    assert_eq!(bez.curvature_at(0.), bez.curvature_at(SMALL_T_DISTANCE));
+    let back = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 0.), v(100., 0.));
+    assert_eq!(
+        back.curvature_at(1.),
+        back.curvature_at(1. - SMALL_T_DISTANCE)
+    );
+
+    let path = Piecewise::new(vec![bez.clone(), bez], None);
+    assert!((path.normal_at(0.5) - normal).magnitude() < 1e-9);
+}
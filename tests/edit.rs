@@ -0,0 +1,258 @@
+use MFEKmath::error::MathError;
+use MFEKmath::{Bezier, Evaluate, Piecewise, Vector};
+
+mod common;
+
+use common::{even_line, v};
+
+fn close(a: &[f64], b: &[f64]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-12)
+}
+
+#[test]
+fn validation() {
+    let segs = vec![
+        even_line(v(0., 0.), v(10., 0.)),
+        even_line(v(10., 0.), v(20., 0.)),
+    ];
+    assert!(Piecewise::try_new(segs.clone(), None).is_ok());
+    assert!(Piecewise::try_new(segs.clone(), Some(vec![0., 0.3, 1.])).is_ok());
+    assert_eq!(
+        Piecewise::try_new(segs.clone(), Some(vec![0., 1.])).err(),
+        Some(MathError::MismatchedCuts { cuts: 2, segs: 2 })
+    );
+    assert_eq!(
+        Piecewise::try_new(segs.clone(), Some(vec![0., 0.7, 0.5])).err(),
+        Some(MathError::UnorderedCuts)
+    );
+    assert!(matches!(
+        Piecewise::try_new(segs.clone(), Some(vec![0., f64::NAN, 1.])),
+        Err(MathError::InvalidParameter(_))
+    ));
+
+    // edits refuse to work from stale cuts
+    let mut path = Piecewise::new(segs, None);
+    path.cuts.pop();
+    assert_eq!(
+        path.append(even_line(v(20., 0.), v(30., 0.))),
+        Err(MathError::MismatchedCuts { cuts: 2, segs: 2 })
+    );
+    assert_eq!(path.segs.len(), 2);
+}
+
+#[test]
+fn inserting_and_appending() {
+    let (a, b, c) = (v(0., 0.), v(10., 0.), v(20., 0.));
+    let mut path = Piecewise::new(vec![even_line(a, b)], None);
+
+    path.append(even_line(b, c)).unwrap();
+    assert!(close(&path.cuts, &[0., 0.5, 1.]));
+    assert_eq!(
+        path.append(even_line(v(25., 0.), v(30., 0.))),
+        Err(MathError::Discontinuous(2))
+    );
+    assert_eq!(path.segs.len(), 2);
+
+    // a segment inserted between two that meet has to start and end where they meet
+    assert_eq!(
+        path.insert_segment(1, even_line(b, c)),
+        Err(MathError::Discontinuous(2))
+    );
+    let lump = Bezier::from_points(b, v(10., 10.), v(20., 10.), b);
+    path.insert_segment(1, lump.clone()).unwrap();
+    assert!(close(&path.cuts, &[0., 1. / 3., 2. / 3., 1.]));
+    assert_eq!(path.at(0.5), lump.at(0.5));
+
+    // one that bridges a gap can go in, and gets the average width
+    let mut gappy = Piecewise::new(
+        vec![even_line(a, b), even_line(c, v(30., 0.))],
+        Some(vec![0., 0.25, 1.]),
+    );
+    gappy.insert_segment(1, even_line(b, c)).unwrap();
+    assert!(close(&gappy.cuts, &[0., 1. / 6., 0.5, 1.]));
+    assert!(gappy.at(1. / 3.).is_near(v(15., 0.), 1e-9));
+
+    // the first segment of an empty piecewise gets all of 0-1
+    let mut empty: Piecewise<Bezier> = Piecewise::new(Vec::new(), None);
+    empty.append(even_line(a, b)).unwrap();
+    assert_eq!(empty.cuts, vec![0., 1.]);
+}
+
+#[test]
+fn removing_and_replacing() {
+    let (a, b, c, d) = (v(0., 0.), v(10., 0.), v(20., 0.), v(30., 0.));
+    let segs = vec![even_line(a, b), even_line(b, c), even_line(c, d)];
+    let mut path = Piecewise::new(segs, Some(vec![0., 0.2, 0.6, 1.]));
+
+    // taking the middle out would leave a gap
+    assert_eq!(
+        path.remove_segment(1).err(),
+        Some(MathError::Discontinuous(1))
+    );
+    assert_eq!(path.segs.len(), 3);
+
+    // the ends can go, and the rest keep their share of t
+    let removed = path.remove_segment(2).unwrap();
+    assert_eq!(removed.end_point(), d);
+    assert!(close(&path.cuts, &[0., 1. / 3., 1.]));
+
+    // a replacement has to meet wherever the old segment did
+    assert_eq!(path.replace_segment(1, even_line(b, d)).map(|_| ()), Ok(()));
+    assert_eq!(
+        path.replace_segment(1, even_line(c, d)).map(|_| ()),
+        Err(MathError::Discontinuous(1))
+    );
+    assert!(close(&path.cuts, &[0., 1. / 3., 1.]));
+    assert_eq!(path.end_point(), d);
+}
+
+#[test]
+fn closed_contours_join_last_to_first() {
+    let (a, b, c, d) = (v(0., 0.), v(10., 0.), v(10., 10.), v(0., 10.));
+    let square = Piecewise::new(
+        vec![
+            even_line(a, b),
+            even_line(b, c),
+            even_line(c, d),
+            even_line(d, a),
+        ],
+        None,
+    );
+    assert!(square.is_closed());
+
+    // the first and last segments hold the contour together like any other two
+    let mut path = square.clone();
+    assert_eq!(
+        path.remove_segment(0).err(),
+        Some(MathError::Discontinuous(0))
+    );
+    assert_eq!(
+        path.remove_segment(3).err(),
+        Some(MathError::Discontinuous(0))
+    );
+    assert_eq!(
+        path.replace_segment(3, even_line(d, b)).err(),
+        Some(MathError::Discontinuous(0))
+    );
+    assert_eq!(
+        path.replace_segment(0, even_line(c, b)).err(),
+        Some(MathError::Discontinuous(0))
+    );
+    assert_eq!(
+        path.append(even_line(a, b)),
+        Err(MathError::Discontinuous(0))
+    );
+    assert_eq!(
+        path.insert_segment(0, even_line(d, b)),
+        Err(MathError::Discontinuous(0))
+    );
+    assert_eq!(path.segs.len(), 4);
+
+    // a detour from the start back to the start fits in at either end
+    let lump = Bezier::from_points(a, v(-5., -5.), v(5., -5.), a);
+    path.append(lump.clone()).unwrap();
+    path.insert_segment(0, lump.clone()).unwrap();
+    assert_eq!(path.segs.len(), 6);
+    assert!(path.is_closed());
+
+    // an open contour's ends are free
+    let mut open = Piecewise::new(square.segs[..3].to_vec(), None);
+    assert!(open.remove_segment(0).is_ok());
+    assert!(open.replace_segment(1, even_line(c, v(5., 20.))).is_ok());
+
+    // concatenating onto a closed contour has to come back to the start
+    let mut closed = square.clone();
+    let detour = Piecewise::new(
+        vec![even_line(a, v(-10., 0.)), even_line(v(-10., 0.), a)],
+        None,
+    );
+    let away = Piecewise::new(vec![even_line(a, v(-10., 0.))], None);
+    assert_eq!(closed.concat(away), Err(MathError::Discontinuous(0)));
+    closed.concat(detour).unwrap();
+    assert_eq!(closed.segs.len(), 6);
+}
+
+#[test]
+fn concatenating_and_splitting() {
+    let (a, b, c) = (v(0., 0.), v(10., 0.), v(20., 0.));
+    let mut path = Piecewise::new(vec![even_line(a, b), even_line(b, c)], None);
+    let other = Piecewise::new(
+        vec![even_line(c, v(30., 0.)), even_line(v(30., 0.), v(40., 0.))],
+        Some(vec![0., 0.9, 1.]),
+    );
+
+    let mut far = Piecewise::new(vec![even_line(v(50., 0.), v(60., 0.))], None);
+    assert_eq!(far.concat(other.clone()), Err(MathError::Discontinuous(1)));
+    assert_eq!(far.segs.len(), 1);
+
+    path.concat(other).unwrap();
+    assert!(close(&path.cuts, &[0., 0.25, 0.5, 0.95, 1.]));
+    assert!(path.at(0.725).is_near(v(25., 0.), 1e-9));
+
+    // splitting moves nothing
+    let before: Vec<Vector> = (0..=20).map(|i| path.at(i as f64 / 20.)).collect();
+    path.split_segment_at(2, 0.25).unwrap();
+    assert_eq!(path.segs.len(), 5);
+    assert!(close(&path.cuts, &[0., 0.25, 0.5, 0.6125, 0.95, 1.]));
+    for (i, point) in before.iter().enumerate() {
+        assert!(path.at(i as f64 / 20.).is_near(*point, 1e-9));
+    }
+    assert!(path.split_segment_at(2, 0.).is_ok());
+    assert_eq!(path.segs.len(), 5);
+    assert_eq!(
+        path.split_segment_at(2, 1.5),
+        Err(MathError::InvalidParameter(1.5))
+    );
+}
+
+#[test]
+fn old_functions_keep_their_cuts() {
+    let arch = Bezier::from_points(v(0., 0.), v(0., 50.), v(100., 50.), v(100., 0.));
+    let path = Piecewise::new(
+        vec![arch, even_line(v(100., 0.), v(0., 0.))],
+        Some(vec![0., 0.8, 1.]),
+    );
+
+    // every segment is split, with a cut for each
+    let subdivided = path.subdivide(0.5);
+    assert_eq!(subdivided.segs.len(), 4);
+    assert!(close(&subdivided.cuts, &[0., 0.4, 0.8, 0.9, 1.]));
+    for i in 0..=20 {
+        let t = i as f64 / 20.;
+        assert!(subdivided.at(t).is_near(path.at(t), 1e-9));
+    }
+
+    assert_eq!(path.balance().cuts, path.cuts);
+
+    let gap = Piecewise::new(
+        vec![
+            even_line(v(0., 0.), v(10., 0.)),
+            even_line(v(10.005, 0.), v(20., 0.)),
+        ],
+        Some(vec![0., 0.3, 1.]),
+    );
+    let fused = gap.fuse_nearby_ends(0.01);
+    assert_eq!(fused.segs[0].end_point(), v(10.005, 0.));
+    assert_eq!(fused.cuts, gap.cuts);
+
+    // a short segment's t goes to the rest, and the one after it is moved back to close the gap
+    let blip = Piecewise::new(
+        vec![
+            even_line(v(0., 0.), v(10., 0.)),
+            even_line(v(10., 0.), v(10.005, 0.)),
+            even_line(v(10.005, 0.), v(20., 0.)),
+        ],
+        Some(vec![0., 0.4, 0.5, 1.]),
+    );
+    let cleaned = blip.remove_short_segs_within(0.01, 0.0001);
+    assert_eq!(cleaned.segs.len(), 2);
+    assert!(close(&cleaned.cuts, &[0., 4. / 9., 1.]));
+    assert_eq!(cleaned.segs[1].start_point(), v(10., 0.));
+    assert_eq!(cleaned.end_point(), v(20., 0.));
+
+    // the old sampled version still drops the blip, but leaves the gap and evens out the cuts
+    let sampled = blip.remove_short_segs(0.01, 4);
+    assert_eq!(sampled.segs.len(), 2);
+    assert_eq!(sampled.cuts, vec![0., 0.5, 1.]);
+    assert_eq!(sampled.segs[1].start_point(), v(10.005, 0.));
+}
@@ -0,0 +1,97 @@
+use std::f64::consts;
+
+use MFEKmath::subdivide::Subdivide;
+use MFEKmath::{EllipticalArc, Evaluate, MathError, Vector};
+
+mod common;
+
+use common::v;
+
+// distance of p from the ellipse in units of its radii, zero on the ellipse
+fn off_ellipse(arc: &EllipticalArc, p: Vector) -> f64 {
+    let local = (p - arc.center).rotate(v(0., 0.), -arc.rotation);
+    let (x, y) = (local.x / arc.radii.x, local.y / arc.radii.y);
+
+    ((x * x + y * y).sqrt() - 1.).abs()
+}
+
+#[test]
+fn svg_arcs() {
+    // the four arcs between two points on a circle of radius 50
+    let (from, to) = (v(0., 0.), v(100., 0.));
+
+    let arc = EllipticalArc::from_svg(from, 50., 50., 0., false, true, to).unwrap();
+    assert!((arc.center - v(50., 0.)).magnitude() < 1e-9);
+    assert!((arc.sweep_angle - consts::PI).abs() < 1e-9);
+    assert!((arc.at(0.5) - v(50., -50.)).magnitude() < 1e-9);
+
+    let arc = EllipticalArc::from_svg(from, 50., 50., 0., false, false, to).unwrap();
+    assert!((arc.at(0.5) - v(50., 50.)).magnitude() < 1e-9);
+
+    // radii that are too small get scaled up until the arc reaches
+    let arc = EllipticalArc::from_svg(from, 10., 10., 0., true, true, to).unwrap();
+    assert!((arc.radii.x - 50.).abs() < 1e-9);
+
+    let arc = EllipticalArc::from_svg(from, 100., 50., 30., true, false, v(80., 60.)).unwrap();
+    assert!((arc.start_point() - from).magnitude() < 1e-9);
+    assert!((arc.end_point() - v(80., 60.)).magnitude() < 1e-9);
+    assert!(arc.sweep_angle < -consts::PI);
+
+    assert!(EllipticalArc::from_svg(from, 0., 50., 0., false, true, to).is_none());
+    assert!(EllipticalArc::from_svg(from, 50., 50., 0., false, true, from).is_none());
+}
+
+#[test]
+fn arc_to_cubics() {
+    let arc = EllipticalArc::new(v(10., 20.), v(300., 100.), 0.4, 0.3, 4.);
+
+    for tolerance in [1., 0.01, 0.0001] {
+        let cubics = arc.to_cubics(tolerance);
+        assert!(cubics.len() >= 3);
+        assert!((cubics.first().unwrap().start_point() - arc.start_point()).magnitude() < 1e-9);
+        assert!((cubics.last().unwrap().end_point() - arc.end_point()).magnitude() < 1e-9);
+
+        for cubic in &cubics {
+            for i in 0..=20 {
+                let p = cubic.at(i as f64 / 20.);
+                assert!(off_ellipse(&arc, p) * 100. <= tolerance);
+            }
+        }
+    }
+
+    assert!(arc.to_cubics(0.0001).len() > arc.to_cubics(1.).len());
+
+    assert_eq!(
+        arc.try_to_cubics(0.).unwrap_err(),
+        MathError::InvalidTolerance(0.)
+    );
+    let endless = EllipticalArc::new(v(10., 20.), v(300., 100.), 0.4, 0.3, f64::INFINITY);
+    assert_eq!(endless.try_to_cubics(1.).unwrap_err(), MathError::NonFinite);
+}
+
+#[test]
+fn evaluate_and_transform() {
+    let arc = EllipticalArc::new(v(0., 0.), v(100., 50.), 0., 0., consts::PI);
+
+    let bounds = arc.bounds();
+    assert!((bounds.left + 100.).abs() < 1e-9);
+    assert!((bounds.right - 100.).abs() < 1e-9);
+    assert!((bounds.top - 50.).abs() < 1e-9);
+    assert!(bounds.bottom.abs() < 1e-9);
+
+    let h = 1e-6;
+    let tangent = (arc.at(0.3 + h) - arc.at(0.3 - h)) / (2. * h);
+    assert!((arc.tangent_at(0.3) - tangent).magnitude() < 1e-4);
+
+    let (first, second) = arc.split(0.25).unwrap();
+    assert!((first.end_point() - arc.at(0.25)).magnitude() < 1e-9);
+    assert!((second.at(0.5) - arc.at(0.625)).magnitude() < 1e-9);
+
+    // shearing and mirroring still leaves us with the same points on an ellipse
+    let transform = |p: &Vector| v(2. * p.x + p.y + 5., -p.y + 3.);
+    let transformed = arc.apply_transform(transform);
+    for i in 0..=10 {
+        let t = i as f64 / 10.;
+        assert!((transformed.at(t) - transform(&arc.at(t))).magnitude() < 1e-9);
+    }
+}
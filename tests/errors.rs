@@ -0,0 +1,144 @@
+use glifparser::glif::contour_operations::vws::{
+    CapType, InterpolationType, VWSContour, VWSHandle,
+};
+use glifparser::{Glif, JoinType};
+#[cfg(feature = "skia")]
+use MFEKmath::pattern_along_path::try_pattern_along_glif;
+use MFEKmath::variable_width_stroking::{
+    try_parse_vws_lib, try_variable_width_stroke, try_variable_width_stroke_glif, VWSSettings,
+};
+use MFEKmath::{
+    ArcLengthParameterization, Bezier, Evaluate, MathError, Parameterization, Piecewise,
+};
+
+mod common;
+
+#[cfg(feature = "skia")]
+use common::pattern_settings;
+use common::v;
+
+#[test]
+fn empty_and_invalid_input() {
+    let empty: Piecewise<Bezier> = Piecewise::new(vec![], None);
+    assert_eq!(empty.try_bounds().unwrap_err(), MathError::EmptyPiecewise);
+    assert_eq!(empty.try_start_point(), Err(MathError::EmptyPiecewise));
+    assert_eq!(empty.try_at(0.5), Err(MathError::EmptyPiecewise));
+    assert_eq!(empty.try_seg_n(0.5), Err(MathError::EmptyPiecewise));
+
+    let bez = Bezier::from_points(v(0., 0.), v(10., 20.), v(30., 20.), v(40., 0.));
+    let path = Piecewise::new(vec![bez.clone(), bez.reverse()], None);
+    assert!(matches!(
+        path.try_at(f64::NAN),
+        Err(MathError::InvalidParameter(_))
+    ));
+    assert!((path.try_at(0.25).unwrap() - bez.at(0.5)).magnitude() < 1e-9);
+    assert_eq!(path.try_at(1.5), Err(MathError::InvalidParameter(1.5)));
+    assert_eq!(path.try_seg_t(-3.), Err(MathError::InvalidParameter(-3.)));
+    assert_eq!(path.try_seg_n(1.), Ok(1));
+
+    let arclen = ArcLengthParameterization { arclens: vec![0.] };
+    assert_eq!(
+        arclen.try_parameterize(0.5),
+        Err(MathError::EmptyParameterization)
+    );
+    assert_eq!(arclen.get_arclen_from_t(0.5), 0.);
+    let arclen = ArcLengthParameterization::from(&path, 100);
+    assert!(arclen.try_parameterize(0.5).is_ok());
+    assert!(arclen.try_parameterize(f64::INFINITY).is_err());
+}
+
+#[test]
+fn errors_know_where_they_came_from() {
+    let bez = Bezier::from_points(v(0., 0.), v(10., 20.), v(30., 20.), v(40., 0.));
+    let outline = Piecewise::new(
+        vec![
+            Piecewise::new(vec![bez], None),
+            Piecewise::new(vec![], None),
+        ],
+        None,
+    );
+
+    let err = outline.try_bounds().unwrap_err();
+    assert_eq!(err.indices(), vec![1]);
+    assert_eq!(err.root_cause(), &MathError::EmptyPiecewise);
+    assert_eq!(err.to_string(), "segment 1: the piecewise has no segments");
+
+    let err = outline.try_end_point().unwrap_err();
+    assert_eq!(err.indices(), vec![1]);
+    assert!(outline.try_start_point().is_ok());
+}
+
+#[test]
+fn stroking_errors() {
+    let handle = || VWSHandle {
+        left_offset: 10.,
+        right_offset: 10.,
+        tangent_offset: 0.,
+        interpolation: InterpolationType::Linear,
+    };
+    let contour = VWSContour {
+        handles: vec![handle(), handle()],
+        cap_start_type: CapType::Round,
+        cap_end_type: CapType::Round,
+        join_type: JoinType::Round,
+        remove_internal: false,
+        remove_external: false,
+    };
+    let settings = VWSSettings::<()> {
+        cap_custom_start: None,
+        cap_custom_end: None,
+    };
+
+    // two segments need a handle at each of their three ends
+    let bez = Bezier::from_points(v(0., 0.), v(10., 20.), v(30., 20.), v(40., 0.));
+    let path = Piecewise::new(vec![bez.clone(), bez.reverse()], None);
+    assert_eq!(
+        try_variable_width_stroke(&path, &contour, &settings).unwrap_err(),
+        MathError::MismatchedHandles {
+            handles: 2,
+            segs: 2
+        }
+    );
+    let empty = Piecewise::new(vec![], None);
+    assert_eq!(
+        try_variable_width_stroke(&empty, &contour, &settings).unwrap_err(),
+        MathError::EmptyPiecewise
+    );
+
+    let glif: Glif<()> = Glif::default();
+    assert!(matches!(
+        try_parse_vws_lib(&glif),
+        Err(MathError::MissingLib(_))
+    ));
+    assert_eq!(
+        try_variable_width_stroke_glif(&glif, settings).unwrap_err(),
+        MathError::MissingOutline
+    );
+}
+
+#[cfg(feature = "skia")]
+#[test]
+fn pattern_errors() {
+    let path: Glif<()> = Glif {
+        outline: Some(vec![vec![]]),
+        ..Glif::default()
+    };
+    let pattern: Glif<()> = Glif::default();
+    assert_eq!(
+        try_pattern_along_glif(&path, &pattern, &pattern_settings(false), None).unwrap_err(),
+        MathError::MissingOutline
+    );
+
+    // the pattern is fine, but there's nothing to lay it along
+    let pattern: Glif<()> = Glif {
+        outline: Some(vec![]),
+        ..Glif::default()
+    };
+    assert_eq!(
+        try_pattern_along_glif(&path, &pattern, &pattern_settings(false), None).unwrap_err(),
+        MathError::InSegment {
+            index: 0,
+            error: Box::new(MathError::EmptyPiecewise)
+        }
+    );
+}
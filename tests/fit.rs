@@ -0,0 +1,152 @@
+use glifparser::{Handle, Point, PointType};
+use MFEKmath::fit_to_points::{fit, fit_curve};
+use MFEKmath::{Bezier, Evaluate, NearestPoint, Piecewise, Vector};
+
+mod common;
+
+use common::v;
+
+// The furthest any of the points is from the fitted path.
+fn max_distance(path: &Piecewise<Bezier>, points: &[Vector]) -> f64 {
+    points
+        .iter()
+        .map(|point| {
+            path.segs
+                .iter()
+                .map(|bez| bez.nearest_point(*point).2)
+                .fold(f64::INFINITY, f64::min)
+        })
+        .fold(0., f64::max)
+}
+
+#[test]
+fn samples_of_a_cubic_give_the_cubic_back() {
+    let original = Bezier::from_points(v(0., 0.), v(30., 100.), v(120., 100.), v(150., 0.));
+    let points: Vec<_> = (0..=50).map(|i| original.at(i as f64 / 50.)).collect();
+
+    let fitted = fit_curve(&points, 0.1, 0.5);
+    assert_eq!(fitted.segs.len(), 1);
+    assert!(max_distance(&fitted, &points) < 0.1);
+    assert_eq!(fitted.start_point(), v(0., 0.));
+    assert_eq!(fitted.end_point(), v(150., 0.));
+}
+
+#[test]
+fn noisy_points() {
+    // a half circle with the points pushed in and out of it
+    let points: Vec<_> = (0..=100)
+        .map(|i| {
+            let angle = std::f64::consts::PI * i as f64 / 100.;
+            let radius = 100. + if i % 2 == 0 { 0.5 } else { -0.5 };
+            v(radius * angle.cos(), radius * angle.sin())
+        })
+        .collect();
+
+    let fitted = fit_curve(&points, 2., 1.);
+    assert!(fitted.segs.len() <= 3);
+    assert!(max_distance(&fitted, &points) < 2.);
+
+    // the noise is smoothed over, so the joins are smooth
+    for pair in fitted.segs.windows(2) {
+        let incoming = pair[0].tangent_at(1.).normalize();
+        let outgoing = pair[1].tangent_at(0.).normalize();
+        assert!(incoming.dot(outgoing) > 0.999);
+    }
+
+    // a tight tolerance has to follow the noise, which takes more segments
+    let tight = fit_curve(&points, 0.1, 1.);
+    assert!(tight.segs.len() > fitted.segs.len());
+    assert!(max_distance(&tight, &points) < 0.1);
+}
+
+#[test]
+fn corners() {
+    // two sides of a square, with points along each side
+    let mut points: Vec<_> = (0..=10).map(|i| v(i as f64 * 10., 0.)).collect();
+    points.extend((1..=10).map(|i| v(100., i as f64 * 10.)));
+
+    let fitted = fit_curve(&points, 0.1, 0.5);
+    assert_eq!(fitted.segs.len(), 2);
+    assert_eq!(fitted.segs[0].end_point(), v(100., 0.));
+    assert!(max_distance(&fitted, &points) < 0.1);
+
+    // with a wide enough angle there's no corner, the points are rounded off instead
+    let rounded = fit_curve(&points, 0.1, 2.);
+    assert!(rounded.segs.windows(2).all(|pair| pair[0]
+        .tangent_at(1.)
+        .normalize()
+        .dot(pair[1].tangent_at(0.).normalize())
+        > 0.999));
+}
+
+#[test]
+fn closed_points() {
+    let points: Vec<_> = (0..=40)
+        .map(|i| {
+            let angle = std::f64::consts::PI * 2. * i as f64 / 40.;
+            v(50. * angle.cos(), 50. * angle.sin())
+        })
+        .collect();
+
+    let fitted = fit_curve(&points, 0.05, 0.5);
+    assert!(fitted.is_closed());
+    assert!(max_distance(&fitted, &points) < 0.05);
+
+    // the seam is smooth too
+    let incoming = fitted.segs.last().unwrap().tangent_at(1.).normalize();
+    let outgoing = fitted.segs[0].tangent_at(0.).normalize();
+    assert!(incoming.dot(outgoing) > 0.999);
+}
+
+#[test]
+fn too_few_points() {
+    assert!(fit_curve(&[], 1., 1.).segs.is_empty());
+    assert!(fit_curve(&[v(1., 1.)], 1., 1.).segs.is_empty());
+
+    let line = fit_curve(&[v(0., 0.), v(30., 0.)], 1., 1.);
+    assert_eq!(line.segs.len(), 1);
+    assert_eq!(line.segs[0].at(0.5), v(15., 0.));
+}
+
+fn handle(handle: Handle) -> Vector {
+    match handle {
+        Handle::At(x, y) => v(x as f64, y as f64),
+        Handle::Colocated => panic!("the point should have a handle"),
+    }
+}
+
+#[test]
+fn closed_contours_are_smooth_all_the_way_around() {
+    let mut contour: Vec<Point<()>> = [(0., 0.), (100., 0.), (150., 80.), (60., 120.), (-20., 60.)]
+        .iter()
+        .map(|at| Point::from_x_y_type(*at, PointType::Curve))
+        .collect();
+    contour[0].name = Some("start".to_string());
+
+    let fitted = fit(vec![contour]).remove(0);
+    assert_eq!(fitted[0].name.as_deref(), Some("start"));
+
+    let n = fitted.len();
+    let beziers: Vec<_> = (0..n)
+        .map(|i| {
+            let (from, to) = (&fitted[i], &fitted[(i + 1) % n]);
+            Bezier::from_points(
+                v(from.x as f64, from.y as f64),
+                handle(from.a),
+                handle(to.b),
+                v(to.x as f64, to.y as f64),
+            )
+        })
+        .collect();
+
+    // the start point joins up as smoothly as the rest
+    for i in 0..n {
+        let (incoming, outgoing) = (&beziers[i], &beziers[(i + 1) % n]);
+        assert!(incoming
+            .tangent_at(1.)
+            .is_near(outgoing.tangent_at(0.), 0.01));
+        assert!(incoming
+            .second_derivative_at(1.)
+            .is_near(outgoing.second_derivative_at(0.), 0.01));
+    }
+}
@@ -0,0 +1,53 @@
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::{Bezier, Evaluate, Intersect, Piecewise};
+
+mod common;
+
+use common::v;
+
+#[test]
+fn crossing_curves() {
+    let arch = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 100.), v(100., 0.));
+    let bar = Bezier::from_points(v(0., 50.), v(33., 50.), v(66., 50.), v(100., 50.));
+
+    let hits = arch.intersections(&bar);
+    assert_eq!(hits.len(), 2);
+    for (t_a, t_b, point) in hits {
+        assert!((point.y - 50.).abs() < 1e-6);
+        assert!(arch.at(t_a).distance(point) < 1e-6);
+        assert!(bar.at(t_b).distance(point) < 1e-3);
+    }
+
+    let quad = QuadBezier::from_points(v(0., 100.), v(50., -100.), v(100., 100.));
+    assert_eq!(arch.intersections(&quad).len(), 2);
+    assert_eq!(quad.intersections(&(v(-10., 10.), v(110., 10.))).len(), 2);
+}
+
+#[test]
+fn tangent_and_overlapping_curves() {
+    let arch = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 100.), v(100., 0.));
+    let touching = Bezier::from_points(v(0., 75.), v(33., 75.), v(66., 75.), v(100., 75.));
+
+    let hits = arch.intersections(&touching);
+    assert_eq!(hits.len(), 1);
+    assert!((hits[0].0 - 0.5).abs() < 1e-3);
+
+    // the second half of the arch, split off and compared with the whole
+    let overlap = Bezier::from_points(v(50., 75.), v(75., 75.), v(100., 50.), v(100., 0.));
+    let hits = arch.intersections(&overlap);
+    assert_eq!(hits.len(), 2);
+    assert!((hits[0].0 - 0.5).abs() < 1e-3 && hits[0].1.abs() < 1e-3);
+    assert!((hits[1].0 - 1.).abs() < 1e-3 && (hits[1].1 - 1.).abs() < 1e-3);
+}
+
+#[test]
+fn piecewise_t_is_global() {
+    let left = Bezier::from_points(v(0., 0.), v(0., 50.), v(0., 50.), v(50., 50.));
+    let right = Bezier::from_points(v(50., 50.), v(100., 50.), v(100., 50.), v(100., 0.));
+    let path = Piecewise::new(vec![left, right], None);
+
+    let hits = path.intersections(&(v(75., -10.), v(75., 100.)));
+    assert_eq!(hits.len(), 1);
+    assert!(hits[0].0 > 0.5);
+    assert!(path.at(hits[0].0).distance(hits[0].2) < 1e-6);
+}
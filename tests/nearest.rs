@@ -0,0 +1,78 @@
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::{Bezier, MathError, NearestPoint, Piecewise};
+
+mod common;
+
+use common::v;
+
+#[test]
+fn nearest_point_on_curves() {
+    let arch = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 100.), v(100., 0.));
+
+    let (t, point, distance) = arch.nearest_point(v(50., 200.));
+    assert!((t - 0.5).abs() < 1e-9);
+    assert!(point.distance(v(50., 75.)) < 1e-9);
+    assert!((distance - 125.).abs() < 1e-9);
+
+    // off the end of the curve the end point is closest
+    let (t, _, distance) = arch.nearest_point(v(-10., -10.));
+    assert_eq!(t, 0.);
+    assert!((distance - 200f64.sqrt()).abs() < 1e-9);
+
+    let quad = QuadBezier::from_points(v(0., 0.), v(50., 100.), v(100., 0.));
+    let (t, point, _) = quad.nearest_point(v(50., 80.));
+    assert!((t - 0.5).abs() < 1e-9);
+    assert!(point.distance(v(50., 50.)) < 1e-9);
+}
+
+#[test]
+fn local_minima_within_radius() {
+    let arch = Bezier::from_points(v(0., 0.), v(0., 100.), v(100., 100.), v(100., 0.));
+
+    // from low down under the arch both legs are local minima and the top is furthest away
+    let hits = arch.nearest_points_within(v(50., 10.), 1000.);
+    assert_eq!(hits.len(), 2);
+    assert!((hits[0].2 - hits[1].2).abs() < 1e-9);
+    assert!((hits[0].0 + hits[1].0 - 1.).abs() < 1e-9);
+    assert!(arch.nearest_points_within(v(50., 10.), 1.).is_empty());
+
+    // inside the circle of curvature at the top, the top is the only minimum
+    let hits = arch.nearest_points_within(v(50., 60.), 1000.);
+    assert_eq!(hits.len(), 1);
+    assert!((hits[0].0 - 0.5).abs() < 1e-9);
+
+    // the joint of two segments is only reported once
+    let left = Bezier::from_points(v(0., 0.), v(0., 50.), v(0., 50.), v(50., 50.));
+    let right = Bezier::from_points(v(50., 50.), v(100., 50.), v(100., 50.), v(100., 0.));
+    let path = Piecewise::new(vec![left, right], None);
+    let hits = path.nearest_points_within(v(50., 60.), 20.);
+    assert_eq!(hits.len(), 1);
+    assert!((hits[0].0 - 0.5).abs() < 1e-9);
+    assert!((hits[0].2 - 10.).abs() < 1e-9);
+}
+
+#[test]
+fn empty_paths_have_no_nearest_point() {
+    let empty: Piecewise<Bezier> = Piecewise::new(vec![], None);
+    assert_eq!(
+        empty.try_nearest_point(v(0., 0.)),
+        Err(MathError::EmptyPiecewise)
+    );
+
+    // an empty contour in an outline says which one it was
+    let bez = Bezier::from_points(v(0., 0.), v(0., 50.), v(100., 50.), v(100., 0.));
+    let outline = Piecewise::new(vec![Piecewise::new(vec![bez.clone()], None), empty], None);
+    assert_eq!(
+        outline
+            .try_nearest_point(v(50., 60.))
+            .unwrap_err()
+            .indices(),
+        vec![1]
+    );
+
+    let path = Piecewise::new(vec![bez], None);
+    assert_eq!(
+        path.try_nearest_point(v(50., 60.)),
+        Ok(path.nearest_point(v(50., 60.)))
+    );
+}
@@ -0,0 +1,83 @@
+use glifparser::glif::contour_operations::vws::CapType;
+use glifparser::JoinType;
+use MFEKmath::fit_to_points::fit_cubic_with_tangents;
+use MFEKmath::{
+    expand_stroke, Area, Bezier, Evaluate, NearestPoint, Orientation, Piecewise, Vector,
+};
+
+mod common;
+
+use common::{circle, v};
+
+#[test]
+fn least_squares_fit() {
+    let bez = Bezier::from_points(v(0., 0.), v(20., 60.), v(90., 70.), v(100., 0.));
+    let ts: Vec<f64> = (0..=10).map(|i| i as f64 / 10.).collect();
+    let points: Vec<Vector> = ts.iter().map(|&t| bez.at(t)).collect();
+
+    let fitted = fit_cubic_with_tangents(&points, &ts, bez.w2 - bez.w1, bez.w4 - bez.w3);
+    assert!(fitted.w2.distance(bez.w2) < 1e-9);
+    assert!(fitted.w3.distance(bez.w3) < 1e-9);
+}
+
+#[test]
+fn offsets_stay_within_tolerance() {
+    // the curve is never tighter than a radius of 15, beyond that the offset has cusps
+    let serpentine = Bezier::from_points(v(0., 0.), v(100., 150.), v(50., -150.), v(200., 0.));
+    for &distance in &[-10., 5., 12.] {
+        for &tolerance in &[0.1, 0.01] {
+            let offset = serpentine.offset(distance, tolerance);
+            assert!(offset.len() > 1);
+
+            for bez in &offset {
+                for i in 0..=10 {
+                    let (_, _, actual) = serpentine.nearest_point(bez.at(i as f64 / 10.));
+                    assert!((actual - f64::abs(distance)).abs() <= tolerance * 2.);
+                }
+            }
+        }
+    }
+
+    // positive distances are on the right, the outside of a counter clockwise circle
+    let offset = circle(v(0., 0.), 100.).offset(10., 0.01);
+    for bez in &offset.segs {
+        assert!((bez.at(0.5).magnitude() - 110.).abs() < 0.1);
+    }
+}
+
+#[test]
+fn expand_strokes() {
+    let line = Piecewise::new(
+        vec![Bezier::from_points(
+            v(0., 0.),
+            v(0., 0.),
+            v(100., 0.),
+            v(100., 0.),
+        )],
+        None,
+    );
+    let open = Piecewise::new(vec![line], None);
+
+    let flat = expand_stroke(&open, 20., JoinType::Miter, CapType::Square);
+    assert_eq!(flat.segs.len(), 1);
+    assert_eq!(
+        flat.segs[0].orientation(),
+        Some(Orientation::CounterClockwise)
+    );
+    assert!((flat.signed_area() - 2000.).abs() < 0.1);
+
+    let round = expand_stroke(&open, 20., JoinType::Round, CapType::Round);
+    let expected = 2000. + std::f64::consts::PI * 100.;
+    assert!((round.signed_area() - expected).abs() < expected * 0.01);
+
+    // a closed contour gives a ring
+    let ring = expand_stroke(
+        &Piecewise::new(vec![circle(v(0., 0.), 100.)], None),
+        20.,
+        JoinType::Round,
+        CapType::Round,
+    );
+    assert_eq!(ring.segs.len(), 2);
+    let expected = std::f64::consts::PI * (110f64.powi(2) - 90f64.powi(2));
+    assert!((ring.signed_area() - expected).abs() < expected * 0.01);
+}
@@ -0,0 +1,129 @@
+use MFEKmath::{
+    AdaptiveArcLengthParameterization, AngleParameterization, ArcLength, ArcLengthParameterization,
+    Bezier, CurvatureParameterization, Evaluate, Parameterization, Piecewise,
+};
+
+mod common;
+
+use common::v;
+
+// A straight run followed by a quarter circle of the same length.
+fn straight_then_bend() -> Piecewise<Bezier> {
+    let k = 0.5522847498 * 100.;
+    Piecewise::new(
+        vec![
+            Bezier::from_points(v(0., 0.), v(50., 0.), v(100., 0.), v(157., 0.)),
+            Bezier::from_points(
+                v(157., 0.),
+                v(157. + k, 0.),
+                v(257., 100. - k),
+                v(257., 100.),
+            ),
+        ],
+        None,
+    )
+}
+
+#[test]
+fn curvature_weight() {
+    let path = straight_then_bend();
+
+    // no weight on curvature is arc length
+    let by_length = CurvatureParameterization::from(&path, 1000, 0.);
+    let arclen = ArcLengthParameterization::from(&path, 1000);
+    for i in 0..=10 {
+        let u = i as f64 / 10.;
+        assert!((by_length.parameterize(u) - arclen.parameterize(u)).abs() < 1e-6);
+    }
+
+    // all of it on curvature skips the straight part, and goes round the bend evenly
+    let by_angle = CurvatureParameterization::from(&path, 1000, 1.);
+    assert!(by_angle.parameterize(0.001) > 0.49);
+    let middle = path.at(by_angle.parameterize(0.5)) - v(157., 100.);
+    assert!((middle.magnitude() - 100.).abs() < 0.1);
+    assert!((middle.x - middle.y.abs()).abs() < 1.);
+
+    // in between, the bend gets more of 0-1 than its share of the length
+    let blended = CurvatureParameterization::from(&path, 1000, 0.5);
+    assert!(blended.parameterize(0.5) > arclen.parameterize(0.5));
+    assert!(blended.parameterize(0.5) < by_angle.parameterize(0.5));
+}
+
+#[test]
+fn curvature_inverse() {
+    let path = straight_then_bend();
+    let blended = CurvatureParameterization::from(&path, 1000, 0.5);
+
+    for i in 0..=10 {
+        let u = i as f64 / 10.;
+        assert!((blended.inverse(blended.parameterize(u)) - u).abs() < 1e-9);
+    }
+    assert_eq!(blended.inverse(1.), 1.);
+
+    let ts = blended.find_parameters_for_intervals(0.25);
+    assert_eq!(ts.len(), 3);
+    assert!(ts.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // a straight line has no curvature to weigh, so it's parameterized by length
+    let line = Bezier::from_points(v(0., 0.), v(10., 0.), v(20., 0.), v(100., 0.));
+    let straight = CurvatureParameterization::from(&line, 1000, 1.);
+    assert!((line.at(straight.parameterize(0.5)).x - 50.).abs() < 0.1);
+}
+
+// u² as a parameterization, which only has the default inverse.
+struct Squared;
+
+impl Parameterization for Squared {
+    fn parameterize(&self, u: f64) -> f64 {
+        u * u
+    }
+}
+
+#[test]
+fn inverses_and_totals() {
+    let path = straight_then_bend();
+    let arclen = AdaptiveArcLengthParameterization::from_arclength(&path, 0.001);
+    let angle = AngleParameterization::from(&path, 1000);
+    let curvature = CurvatureParameterization::from(&path, 1000, 0.5);
+
+    let parameterizations: [&dyn Parameterization; 3] = [&arclen, &angle, &curvature];
+    for parameterization in parameterizations.iter() {
+        for i in 0..=10 {
+            let u = i as f64 / 10.;
+            let t = parameterization.parameterize(u);
+            assert!((parameterization.inverse(t) - u).abs() < 1e-6);
+        }
+        assert!((parameterization.inverse(1.) - 1.).abs() < 1e-9);
+    }
+
+    assert_eq!(arclen.total(), arclen.get_total_arclen());
+    assert!((angle.total() - std::f64::consts::FRAC_PI_2).abs() < 0.01);
+    assert!((Squared.inverse(0.25) - 0.5).abs() < 1e-12);
+    assert_eq!(Squared.total(), 1.);
+
+    // the end of the last interval of the angle table is the total
+    assert_eq!(angle.get_angle_from_t(1.), angle.get_total_angle());
+    let before_end = angle.get_angle_from_t(0.9995);
+    assert!(before_end > angle.total_angles[999] && before_end <= angle.total_angles[1000]);
+}
+
+#[test]
+fn combinators() {
+    let path = straight_then_bend();
+    let arclen = AdaptiveArcLengthParameterization::from_arclength(&path, 0.001);
+
+    // just the bend, by arc length
+    let bend = (&arclen).restrict(0.5, 1.);
+    assert!((bend.parameterize(0.) - 0.5).abs() < 1e-6);
+    assert!((bend.parameterize(1.) - 1.).abs() < 1e-6);
+    assert!((bend.total() - path.segs[1].arclen(1e-9)).abs() < 0.01);
+    let middle = path.at(bend.parameterize(0.5)) - v(157., 100.);
+    assert!((middle.x - middle.y.abs()).abs() < 0.5);
+    assert!((bend.inverse(bend.parameterize(0.3)) - 0.3).abs() < 1e-6);
+
+    // easing in along the path
+    let eased = Squared.then(&arclen);
+    assert!((eased.parameterize(0.5) - arclen.parameterize(0.25)).abs() < 1e-12);
+    assert!((eased.inverse(arclen.parameterize(0.25)) - 0.5).abs() < 1e-6);
+    assert_eq!(eased.total(), 1.);
+}
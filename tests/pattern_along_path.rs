@@ -0,0 +1,33 @@
+#![cfg(feature = "skia")]
+
+use MFEKmath::{skia_safe, split_and_blit, Piecewise};
+
+mod common;
+
+use common::{line, pattern_settings, rectangle, v};
+
+#[test]
+fn reverse_path_starts_from_the_other_end() {
+    // a long straight path, so arc lengths and fractions of it are far apart
+    let path = Piecewise::new(vec![line(v(0., 0.), v(1000., 0.))], None);
+    let pattern = Piecewise::new(vec![rectangle(v(0., 0.), 100., 20.)], None);
+
+    let forwards = split_and_blit(
+        &path,
+        &pattern,
+        &pattern_settings(false),
+        &mut skia_safe::Path::new(),
+    );
+    let bounds = forwards.bounds();
+    assert!(bounds.left.abs() < 0.01 && (bounds.right - 100.).abs() < 0.01);
+
+    let backwards = split_and_blit(
+        &path,
+        &pattern,
+        &pattern_settings(true),
+        &mut skia_safe::Path::new(),
+    );
+    let bounds = backwards.bounds();
+    assert!((bounds.left - 900.).abs() < 0.01 && (bounds.right - 1000.).abs() < 0.01);
+    assert!(((bounds.top - bounds.bottom).abs() - 20.).abs() < 0.01);
+}
@@ -0,0 +1,186 @@
+use MFEKmath::conic::Conic;
+use MFEKmath::ellipticalarc::EllipticalArc;
+use MFEKmath::error::MathError;
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::subdivide::Subdivide;
+use MFEKmath::{Bezier, Evaluate, Piecewise, Rect, Vector};
+
+mod common;
+
+use common::{even_line, v};
+
+fn square() -> Piecewise<Bezier> {
+    let corners = [v(0., 0.), v(100., 0.), v(100., 100.), v(0., 100.)];
+    let segs = (0..4)
+        .map(|i| even_line(corners[i], corners[(i + 1) % 4]))
+        .collect();
+    Piecewise::new(segs, None)
+}
+
+// A cubic that can only be split, so it gets the default subsegment.
+#[derive(Clone)]
+struct SplitOnly(Bezier);
+
+impl Subdivide for SplitOnly {
+    fn split(&self, t: f64) -> Option<(Self, Self)> {
+        self.0.split(t).map(|(a, b)| (SplitOnly(a), SplitOnly(b)))
+    }
+}
+
+impl Evaluate for SplitOnly {
+    fn at(&self, t: f64) -> Vector {
+        self.0.at(t)
+    }
+    fn tangent_at(&self, t: f64) -> Vector {
+        self.0.tangent_at(t)
+    }
+    fn second_derivative_at(&self, t: f64) -> Vector {
+        self.0.second_derivative_at(t)
+    }
+    fn bounds(&self) -> Rect {
+        self.0.bounds()
+    }
+    fn apply_transform<F>(&self, transform: F) -> Self
+    where
+        F: Fn(&Vector) -> Vector + Send + Sync,
+    {
+        SplitOnly(self.0.apply_transform(transform))
+    }
+    fn start_point(&self) -> Vector {
+        self.0.start_point()
+    }
+    fn end_point(&self) -> Vector {
+        self.0.end_point()
+    }
+}
+
+#[test]
+fn default_subsegments() {
+    let bez = Bezier::from_points(v(0., 0.), v(20., 60.), v(90., 70.), v(100., 0.));
+    let curve = SplitOnly(bez.clone());
+
+    let part = curve.subsegment(0.2, 0.7);
+    for i in 0..=10 {
+        let u = i as f64 / 10.;
+        assert!(part.at(u).is_near(bez.at(0.2 + 0.5 * u), 1e-9));
+    }
+
+    // nothing lies between t0 and t1, so every point of what's left sits at t0
+    let backwards = curve.subsegment(0.7, 0.2);
+    let empty = curve.subsegment(0.4, 0.4);
+    let before = curve.subsegment(-0.5, -0.2);
+    for u in [0., 0.5, 1.] {
+        assert!(backwards.at(u).is_near(bez.at(0.7), 1e-12));
+        assert!(empty.at(u).is_near(bez.at(0.4), 1e-12));
+        assert_eq!(before.at(u), bez.start_point());
+    }
+}
+
+#[test]
+fn subsegments() {
+    let bez = Bezier::from_points(v(0., 0.), v(20., 60.), v(90., 70.), v(100., 0.));
+    let part = bez.subsegment(0.2, 0.7);
+    for i in 0..=10 {
+        let u = i as f64 / 10.;
+        assert!(part.at(u).is_near(bez.at(0.2 + 0.5 * u), 1e-9));
+    }
+    // the ends at 0 and 1 don't need splitting
+    assert_eq!(
+        bez.subsegment(0., 1.).to_control_points(),
+        bez.to_control_points()
+    );
+    assert!(bez.subsegment(0., 0.4).at(1.).is_near(bez.at(0.4), 1e-12));
+    assert!(bez.subsegment(0.4, 1.).at(0.).is_near(bez.at(0.4), 1e-12));
+
+    let quad = QuadBezier::from_points(v(0., 0.), v(50., 80.), v(100., 0.));
+    let part = quad.subsegment(0.3, 0.9);
+    for i in 0..=10 {
+        let u = i as f64 / 10.;
+        assert!(part.at(u).is_near(quad.at(0.3 + 0.6 * u), 1e-9));
+    }
+
+    let conic = Conic::from_points(v(0., 0.), v(50., 80.), v(100., 0.), 0.7);
+    assert!(conic
+        .subsegment(0.25, 0.5)
+        .at(0.)
+        .is_near(conic.at(0.25), 1e-9));
+    let arc = EllipticalArc::new(v(0., 0.), v(50., 20.), 0.3, 0., 3.);
+    let part = Subdivide::subsegment(&arc, 0.25, 0.5);
+    assert!(part.at(0.5).is_near(arc.at(0.375), 1e-9));
+}
+
+#[test]
+fn portions() {
+    let path = square();
+
+    // from the middle of the first side to the middle of the third
+    let part = path.portion(0.125, 0.625);
+    assert_eq!(part.segs.len(), 3);
+    assert_eq!(part.cuts, vec![0., 0.25, 0.75, 1.]);
+    for i in 0..=20 {
+        let u = i as f64 / 20.;
+        assert!(part.at(u).is_near(path.at(0.125 + 0.5 * u), 1e-9));
+    }
+
+    // ends right on joins don't leave empty pieces behind
+    let part = path.portion(0.25, 0.5);
+    assert_eq!(part.segs.len(), 1);
+    assert_eq!(part.cuts, vec![0., 1.]);
+    assert!(part.at(0.).is_near(v(100., 0.), 1e-12));
+    assert!(part.at(1.).is_near(v(100., 100.), 1e-12));
+
+    // within a single segment
+    let part = path.portion(0.3, 0.4);
+    assert_eq!(part.segs.len(), 1);
+    assert!(part.at(0.5).is_near(path.at(0.35), 1e-9));
+
+    // the cuts keep each piece's share of t when the original cuts aren't even
+    let uneven = Piecewise::new(path.segs.clone(), Some(vec![0., 0.1, 0.5, 0.6, 1.]));
+    let part = uneven.portion(0.05, 0.55);
+    assert_eq!(part.segs.len(), 3);
+    assert!((part.cuts[1] - 0.1).abs() < 1e-12 && (part.cuts[2] - 0.9).abs() < 1e-12);
+    assert!(part.at(0.5).is_near(uneven.at(0.3), 1e-9));
+
+    // a portion with no length at all
+    let part = path.portion(0.3, 0.3);
+    assert_eq!(part.segs.len(), 1);
+    assert!(part.at(1.).is_near(path.at(0.3), 1e-9));
+}
+
+#[test]
+fn wrapping_portions() {
+    let path = square();
+    assert!(path.is_closed());
+
+    // across the start of the contour, from halfway up the left side to a quarter along the bottom
+    let part = path.portion(0.875, 0.0625);
+    assert_eq!(part.segs.len(), 2);
+    assert!((part.cuts[1] - 2. / 3.).abs() < 1e-12);
+    assert!(part.at(0.).is_near(v(0., 50.), 1e-9));
+    assert!(part.at(2. / 3.).is_near(v(0., 0.), 1e-9));
+    assert!(part.at(1.).is_near(v(25., 0.), 1e-9));
+
+    // wraps that end or start right on the seam don't pick up an empty piece there
+    let part = path.portion(0.5, 0.);
+    assert_eq!(part.segs.len(), 2);
+    assert_eq!(part.cuts, vec![0., 0.5, 1.]);
+    assert!(part.at(1.).is_near(v(0., 0.), 1e-12));
+    let part = path.portion(1., 0.25);
+    assert_eq!(part.segs.len(), 1);
+    assert_eq!(part.cuts, vec![0., 1.]);
+    assert!(part.at(1.).is_near(v(100., 0.), 1e-12));
+    let part = path.portion(1., 0.);
+    assert_eq!(part.segs.len(), 1);
+
+    // an open path has nothing to wrap around, so the ends are swapped
+    let open = Piecewise::new(path.segs[..3].to_vec(), None);
+    let part = open.portion(0.9, 0.1);
+    assert!(part.at(0.).is_near(open.at(0.1), 1e-9));
+    assert!(part.at(1.).is_near(open.at(0.9), 1e-9));
+
+    assert!(matches!(
+        path.try_portion(0.5, f64::NAN),
+        Err(MathError::InvalidParameter(_))
+    ));
+    assert!(path.try_portion(0.875, 0.0625).is_ok());
+}
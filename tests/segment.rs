@@ -0,0 +1,59 @@
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::subdivide::Subdivide;
+use MFEKmath::{Bezier, Evaluate, Line, NearestPoint, Piecewise, Segment};
+
+mod common;
+
+use common::v;
+
+#[test]
+fn line_matches_its_cubic() {
+    let line = Line::from_points(v(0., 0.), v(90., 30.));
+    let cubic = line.to_cubic();
+
+    for i in 0..=10 {
+        let t = i as f64 / 10.;
+        assert!(line.at(t).distance(cubic.at(t)) < 1e-9);
+    }
+
+    let (left, right) = line.split(0.25).unwrap();
+    assert!(left.end_point().is_near(v(22.5, 7.5), 1e-9));
+    assert!(right.start_point().is_near(left.end_point(), 0.));
+
+    let (t, _, distance) = line.nearest_point(v(30., 10.));
+    assert!((t - 1. / 3.).abs() < 1e-9);
+    assert!(distance < 1e-9);
+}
+
+#[test]
+fn mixed_piecewise() {
+    let degenerate_line = Bezier::from_points(v(0., 0.), v(0., 0.), v(100., 0.), v(100., 0.));
+    let curve = Bezier::from_points(v(100., 0.), v(100., 100.), v(0., 100.), v(0., 0.));
+    let cubics = Piecewise::new(vec![degenerate_line, curve], None);
+
+    let mixed = Piecewise::<Segment>::from(&cubics);
+    assert!(matches!(mixed.segs[0], Segment::Line(_)));
+    assert!(matches!(mixed.segs[1], Segment::Cubic(_)));
+    assert_eq!(mixed.cuts, cubics.cuts);
+
+    let with_quad = Piecewise::new(
+        vec![
+            mixed.segs[0].clone(),
+            Segment::Quad(QuadBezier::from_points(
+                v(100., 0.),
+                v(50., 100.),
+                v(0., 0.),
+            )),
+        ],
+        None,
+    );
+    let as_cubics = with_quad.to_cubic();
+    for i in 0..=10 {
+        let t = i as f64 / 10.;
+        assert!(with_quad.at(t).distance(as_cubics.at(t)) < 1e-9);
+    }
+
+    let reversed = with_quad.segs[1].reverse();
+    assert!(reversed.start_point().is_near(v(0., 0.), 0.));
+    assert!(reversed.end_point().is_near(v(100., 0.), 0.));
+}
@@ -0,0 +1,74 @@
+use MFEKmath::subdivide::Subdivide;
+use MFEKmath::{Bezier, Evaluate, NearestPoint, Piecewise};
+
+mod common;
+
+use common::{line, v};
+
+#[test]
+fn merges_split_curves() {
+    let bez = Bezier::from_points(v(0., 0.), v(30., 120.), v(170., 120.), v(200., 0.));
+    let pieces = bez.split_at_multiple_t(vec![0.2, 0.45, 0.5, 0.8]);
+    let path = Piecewise::new(pieces, None);
+
+    let (simplified, removed) = path.simplify(0.01, 0.01);
+    assert_eq!(simplified.segs.len(), 1);
+    assert_eq!(removed, 4);
+    for i in 0..=20 {
+        let (_, _, distance) = bez.nearest_point(simplified.at(i as f64 / 20.));
+        assert!(distance < 0.01);
+    }
+}
+
+#[test]
+fn keeps_corners_and_shape() {
+    let arch = Bezier::from_points(v(100., 0.), v(100., 80.), v(200., 80.), v(200., 0.));
+    let (left, right) = arch.split(0.5).unwrap();
+
+    // two straight lines in a row merge, but not across the corner into the arch
+    let path = Piecewise::new(
+        vec![
+            line(v(0., 0.), v(50., 0.)),
+            line(v(50., 0.), v(100., 0.)),
+            left,
+            right,
+        ],
+        None,
+    );
+    let (simplified, removed) = path.simplify(0.01, 0.01);
+    assert_eq!(removed, 2);
+    assert_eq!(simplified.segs.len(), 2);
+    assert_eq!(simplified.segs[0].w4, v(100., 0.));
+
+    // however loose the tolerance, lines with colocated handles make corners with curves
+    let (simplified, removed) = path.simplify(1000., 0.01);
+    assert_eq!(removed, 2);
+    assert_eq!(simplified.segs[0].w4, v(100., 0.));
+
+    // and so does a curve with a handle on top of its point
+    let dip = Bezier::from_points(v(0., 0.), v(50., 50.), v(100., 0.), v(100., 0.));
+    let rise = Bezier::from_points(v(100., 0.), v(150., 50.), v(200., 50.), v(200., 100.));
+    let corner = Piecewise::new(vec![dip, rise], None);
+    let (simplified, removed) = corner.simplify(1000., 0.01);
+    assert_eq!(removed, 0);
+    assert_eq!(simplified.segs[0].w4, v(100., 0.));
+    assert_eq!(corner.split_at_tangent_discontinuities(0.01).segs.len(), 2);
+
+    // unless the corner angle is wider than the corner
+    let (simplified, removed) = corner.simplify(1000., std::f64::consts::FRAC_PI_2 + 0.1);
+    assert_eq!(removed, 1);
+    assert_eq!(simplified.segs[0].w4, v(200., 100.));
+
+    // an S can't be a single cubic within the tolerance, so nothing changes
+    let s = Piecewise::new(
+        vec![
+            Bezier::from_points(v(0., 0.), v(0., 50.), v(100., 50.), v(100., 0.)),
+            Bezier::from_points(v(100., 0.), v(100., -50.), v(200., -50.), v(200., 0.)),
+            Bezier::from_points(v(200., 0.), v(200., 50.), v(300., 50.), v(300., 0.)),
+        ],
+        None,
+    );
+    let (simplified, removed) = s.simplify(0.5, 0.01);
+    assert_eq!(removed, 0);
+    assert_eq!(simplified.segs.len(), 3);
+}
@@ -0,0 +1,70 @@
+use MFEKmath::quadbezier::QuadBezier;
+use MFEKmath::{Bezier, FillRule, Piecewise, Vector, Winding};
+
+mod common;
+
+use common::{circle, polygon, v};
+
+#[test]
+fn polygons_and_vertices() {
+    let square = polygon(&[v(0., 0.), v(10., 0.), v(10., 10.), v(0., 10.)]);
+    assert_eq!(square.winding_number(v(5., 5.)), 1);
+    assert_eq!(square.winding_number(v(15., 5.)), 0);
+    assert_eq!(square.winding_number(v(-5., 5.)), 0);
+    assert_eq!(square.winding_number(v(5., 0.)), 1);
+    assert_eq!(
+        polygon(&[v(0., 0.), v(0., 10.), v(10., 10.), v(10., 0.)]).winding_number(v(5., 5.)),
+        -1
+    );
+
+    // the ray from the center runs right through a vertex
+    let diamond = polygon(&[v(10., 0.), v(0., 10.), v(-10., 0.), v(0., -10.)]);
+    assert_eq!(diamond.winding_number(v(0., 0.)), 1);
+    assert_eq!(diamond.winding_number(v(-20., 0.)), 0);
+    assert_eq!(diamond.winding_number(v(-20., 10.)), 0);
+
+    // contours that aren't closed get closed with a line
+    let open = Piecewise::new(
+        vec![
+            Bezier::from_points(v(0., 0.), v(0., 0.), v(10., 0.), v(10., 0.)),
+            Bezier::from_points(v(10., 0.), v(10., 0.), v(10., 10.), v(10., 10.)),
+        ],
+        None,
+    );
+    assert_eq!(open.winding_number(v(8., 2.)), 1);
+    assert_eq!(open.winding_number(v(2., 8.)), 0);
+}
+
+#[test]
+fn curves_and_fill_rules() {
+    let outer = circle(v(0., 0.), 100.);
+    let inner = circle(v(0., 0.), 50.);
+    let outline = Piecewise::new(vec![outer.clone(), inner], None);
+
+    assert_eq!(outline.winding_number(v(10., 10.)), 2);
+    assert!(outline.contains(v(10., 10.), FillRule::NonZero));
+    assert!(!outline.contains(v(10., 10.), FillRule::EvenOdd));
+    assert!(outline.contains(v(75., 0.), FillRule::EvenOdd));
+
+    // just inside and just outside of the top of the curve
+    assert_eq!(outer.winding_number(v(0., 99.99)), 1);
+    assert_eq!(outer.winding_number(v(0., 100.01)), 0);
+    assert_eq!(outer.winding_number(v(70.6, 70.6)), 1);
+    assert_eq!(outer.winding_number(v(70.8, 70.8)), 0);
+
+    let quads = Piecewise::new(
+        vec![
+            QuadBezier::from_points(v(0., 0.), v(50., -50.), v(100., 0.)),
+            QuadBezier::from_points(v(100., 0.), v(50., 50.), v(0., 0.)),
+        ],
+        None,
+    );
+    assert_eq!(quads.winding_number(v(50., 20.)), 1);
+    assert_eq!(quads.winding_number(v(50., 30.)), 0);
+
+    let points: Vec<Vector> = (0..50).map(|i| v(i as f64 * 5. - 120., 3.)).collect();
+    let batched = outline.contains_points(&points, FillRule::EvenOdd);
+    for (point, inside) in points.iter().zip(batched) {
+        assert_eq!(outline.contains(*point, FillRule::EvenOdd), inside);
+    }
+}